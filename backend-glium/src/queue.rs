@@ -2,8 +2,8 @@ use crate::shared_res::SharedResources;
 use crate::vertex::{RectVertex, Vertex};
 use glium::index::PrimitiveType;
 use glium::{uniform, Surface};
-use rtk::backend::{DrawBackend, Resources, TextureError};
-use rtk::draw::{Color, FillMode, TextSection, TextureId};
+use rtk::backend::{Clipboard, DrawBackend, Resources, TextureError};
+use rtk::draw::{Color, FillMode, GlyphPosition, TextSection, TextureId};
 use rtk::font::{FontFamily, FontId, FontLoadError, FontProperties, FontSource};
 use rtk::geometry::{Point, Rect, Size};
 use rtk::image::Image;
@@ -242,6 +242,21 @@ impl Resources for DrawQueue<'_> {
     fn load_font(&mut self, font_src: &FontSource) -> Result<FontId, FontLoadError> {
         self.shared_res.load_font(font_src)
     }
+
+    #[inline]
+    fn measure_text(&mut self, text: &TextSection) -> Option<Rect> {
+        self.shared_res.measure_text(text)
+    }
+
+    #[inline]
+    fn glyph_positions(&mut self, text: &TextSection) -> Vec<GlyphPosition> {
+        self.shared_res.glyph_positions(text)
+    }
+
+    #[inline]
+    fn clipboard(&mut self) -> Option<&mut dyn Clipboard> {
+        self.shared_res.clipboard()
+    }
 }
 
 /// A single draw command.
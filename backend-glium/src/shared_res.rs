@@ -9,11 +9,13 @@ use glium::glutin::{Api, ContextBuilder, GlProfile, GlRequest, NotCurrent, Robus
 use glium::texture::{ClientFormat, MipmapsOption, RawImage2d, SrgbTexture2d, Texture2d, TextureCreationError};
 use glyph_brush::ab_glyph::FontVec;
 use glyph_brush::{BrushAction, BrushError};
-use glyph_brush::{Extra, FontId, GlyphBrush, GlyphBrushBuilder};
-use rtk::backend::{Resources, TextureError};
-use rtk::draw::TextureId;
+use glyph_brush::{Extra, FontId, GlyphBrush, GlyphBrushBuilder, GlyphCruncher};
+use rtk::backend::{Clipboard, Resources, TextureError};
+use rtk::draw::{GlyphPosition, TextSection, TextureId};
 use rtk::font::{FontLoadError, FontSource};
+use rtk::geometry::{Point, Rect};
 use rtk::image::{Image, ImageData, PixelFormat};
+use rtk_winit::SystemClipboard;
 use std::borrow::Cow;
 use std::collections::{hash_map, HashMap};
 use std::fmt;
@@ -39,6 +41,8 @@ pub struct SharedResources {
     pub(crate) glyph_brush: GlyphBrush<RectVertex, Extra, FontVec>,
     /// Font texture cache.
     pub(crate) font_tex: FontTex,
+    /// System clipboard, if available.
+    clipboard: Option<SystemClipboard>,
 }
 
 // pls implement Debug on your types..
@@ -54,6 +58,7 @@ impl fmt::Debug for SharedResources {
             .field("loaded_fonts", &self.loaded_fonts)
             .field("glyph_brush", &self.glyph_brush)
             .field("font_tex", &self.font_tex)
+            .field("clipboard", &self.clipboard)
             .finish()
     }
 }
@@ -90,6 +95,7 @@ impl SharedResources {
             texture_map: Default::default(),
             loaded_fonts: Default::default(),
             glyph_brush,
+            clipboard: SystemClipboard::new(),
         };
 
         let default_font = this.select_font(&[FamilyName::SansSerif], &Default::default()).unwrap();
@@ -166,6 +172,26 @@ impl Resources for SharedResources {
             Ok(id)
         }
     }
+
+    fn measure_text(&mut self, text: &TextSection) -> Option<Rect> {
+        self.glyph_brush
+            .glyph_bounds(text)
+            .map(|b| Rect::enclosing(Point::new(b.min.x, b.min.y), Point::new(b.max.x, b.max.y)))
+    }
+
+    fn glyph_positions(&mut self, text: &TextSection) -> Vec<GlyphPosition> {
+        let glyphs: Vec<_> = self.glyph_brush.glyphs(text).cloned().collect();
+        let fonts = self.glyph_brush.fonts();
+        glyphs
+            .iter()
+            .map(|g| GlyphPosition::from_section_glyph(g, &fonts[g.font_id.0]))
+            .collect()
+    }
+
+    #[inline]
+    fn clipboard(&mut self) -> Option<&mut dyn Clipboard> {
+        self.clipboard.as_mut().map(|c| c as &mut dyn Clipboard)
+    }
 }
 
 #[derive(Debug)]
@@ -5,7 +5,8 @@ use glium::glutin::event_loop::EventLoop;
 use glium::glutin::window::WindowId;
 use rtk::event::Event;
 use rtk::toplevel::{TopLevel, WindowAttributes};
-use rtk_winit::{make_win_builder, BackendWindow};
+use rtk::widget::WidgetId;
+use rtk_winit::{ime_position, make_win_builder, BackendWindow};
 use std::fmt;
 
 pub struct GliumWindow<T> {
@@ -61,6 +62,9 @@ impl<T: TopLevel> BackendWindow<SharedResources> for GliumWindow<T> {
         }
 
         self.window.update_layout(resources);
+        if let Some(area) = self.window.ime_cursor_area() {
+            self.display.gl_window().window().set_ime_position(ime_position(area));
+        }
         //TODO: compare `self.cur_attr` with `self.window.get_window_attributes()` to make changes to real window
     }
 
@@ -74,6 +78,10 @@ impl<T: TopLevel> BackendWindow<SharedResources> for GliumWindow<T> {
         self.display.gl_window().window().request_redraw();
     }
 
+    fn scale_factor(&self) -> f64 {
+        self.display.gl_window().window().scale_factor()
+    }
+
     fn push_event(&mut self, event: Event) -> bool {
         match event {
             Event::Resized(size) => {
@@ -88,4 +96,8 @@ impl<T: TopLevel> BackendWindow<SharedResources> for GliumWindow<T> {
         }
         self.window.push_event(event)
     }
+
+    fn push_event_to(&mut self, target: WidgetId, event: Event) -> bool {
+        self.window.push_event_to(target, event)
+    }
 }
@@ -0,0 +1,69 @@
+use rtk::backend::{Clipboard, ClipboardError};
+use rtk::image::{Image, ImageData, PixelFormat};
+use std::fmt;
+
+/// System clipboard implementation.
+pub struct SystemClipboard(arboard::Clipboard);
+
+impl SystemClipboard {
+    /// Opens the system clipboard. Returns `None` if the platform clipboard isn't available.
+    #[inline]
+    pub fn new() -> Option<Self> {
+        arboard::Clipboard::new().ok().map(SystemClipboard)
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.0.get_text().ok()
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.0.set_text(text).map_err(translate_error)
+    }
+
+    fn get_image(&mut self) -> Option<Image> {
+        let image = self.0.get_image().ok()?;
+        let size = [image.width as u32, image.height as u32];
+        Some(Image::new(image.bytes.into_owned(), size, PixelFormat::Rgba))
+    }
+
+    fn set_image(&mut self, image: &Image) -> Result<(), ClipboardError> {
+        let bytes = to_rgba(image).ok_or(ClipboardError::FormatNotSupported)?;
+        let size = image.get_size();
+        let image = arboard::ImageData {
+            width: size.w as usize,
+            height: size.h as usize,
+            bytes: bytes.into(),
+        };
+        self.0.set_image(image).map_err(translate_error)
+    }
+}
+
+impl fmt::Debug for SystemClipboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SystemClipboard")
+    }
+}
+
+fn translate_error(err: arboard::Error) -> ClipboardError {
+    match err {
+        arboard::Error::ConversionFailure => ClipboardError::FormatNotSupported,
+        _ => ClipboardError::Unavailable,
+    }
+}
+
+/// Converts an 8-bit image into RGBA pixels.
+fn to_rgba(image: &Image) -> Option<Vec<u8>> {
+    let data = match image.get_data() {
+        Some(ImageData::U8(data)) => data,
+        _ => return None,
+    };
+    let rgba = match image.get_format() {
+        PixelFormat::Luma => data.iter().flat_map(|&l| [l, l, l, 255]).collect(),
+        PixelFormat::LumaA => data.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        PixelFormat::Rgb => data.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        PixelFormat::Rgba => data.clone(),
+    };
+    Some(rgba)
+}
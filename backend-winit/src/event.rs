@@ -1,9 +1,15 @@
-use rtk::event::{Axis, ButtonState, Event, Key, KeyModState, KeySide};
+use rtk::event::{Axis, ButtonState, Event, Key, KeyModState, KeySide, ScrollDelta, ScrollPhase};
 use rtk::geometry::{Point, Size};
 use winit::dpi::{PhysicalPosition, PhysicalSize};
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase, VirtualKeyCode, WindowEvent};
 
-pub fn translate_event(event: WindowEvent) -> Option<Event> {
+/// Translates a winit event. The scale factor of the window is used to convert logical units into physical pixels.
+///
+/// winit 0.22 has no input method events, so `ImePreedit` and `ImeCommit` are never produced. Composed text is
+/// received as `Character` events once committed, and the composition itself is drawn by the system. Translating the
+/// composition needs `WindowEvent::Ime` from winit 0.27, which also requires moving the glium backend to a glutin
+/// release based on it.
+pub fn translate_event(event: WindowEvent, scale_factor: f64) -> Option<Event> {
     use rtk::event::Event::*;
 
     Some(match event {
@@ -12,6 +18,7 @@ pub fn translate_event(event: WindowEvent) -> Option<Event> {
         WindowEvent::CloseRequested => CloseRequest,
         WindowEvent::Destroyed => Destroyed,
         WindowEvent::DroppedFile(path) => FileDropped(path),
+        //TODO: translate WindowEvent::Ime into ImePreedit/ImeCommit after upgrading winit, see above
         WindowEvent::ReceivedCharacter(chr) => Character(chr),
         WindowEvent::Focused(is_focused) => Focused(is_focused),
         WindowEvent::KeyboardInput { input, .. } => Keyboard {
@@ -31,10 +38,25 @@ pub fn translate_event(event: WindowEvent) -> Option<Event> {
         } => MouseMoved(Axis::Position(Point { x, y })),
         WindowEvent::CursorEntered { .. } => PointerInside(true),
         WindowEvent::CursorLeft { .. } => PointerInside(false),
-        WindowEvent::MouseWheel {
-            delta: MouseScrollDelta::LineDelta(x, y),
-            ..
-        } => MouseMoved(Axis::Scroll(x, y)),
+        WindowEvent::MouseWheel { delta, phase, .. } => {
+            let delta = match delta {
+                MouseScrollDelta::LineDelta(x, y) => ScrollDelta::Lines(x, y),
+                MouseScrollDelta::PixelDelta(pos) => {
+                    let PhysicalPosition { x, y } = pos.to_physical(scale_factor);
+                    ScrollDelta::Pixels(x, y)
+                }
+            };
+            MouseMoved(Axis::Scroll(delta, translate_phase(phase)))
+        }
+        WindowEvent::Touch(touch) => Touch {
+            id: touch.id,
+            phase: translate_touch_phase(touch.phase),
+            pos: Point {
+                x: touch.location.x,
+                y: touch.location.y,
+            },
+            force: touch.force.map(|force| force.normalized()),
+        },
         WindowEvent::MouseInput { state, button, .. } => MouseButton(translate_state(state), translate_button(button)),
         WindowEvent::TouchpadPressure { pressure, .. } => MouseMoved(Axis::Pressure(pressure as f64)),
         _ => {
@@ -50,6 +72,25 @@ fn translate_state(state: ElementState) -> ButtonState {
     }
 }
 
+fn translate_phase(phase: TouchPhase) -> ScrollPhase {
+    match phase {
+        TouchPhase::Started => ScrollPhase::Started,
+        TouchPhase::Moved => ScrollPhase::Moved,
+        TouchPhase::Ended | TouchPhase::Cancelled => ScrollPhase::Ended,
+    }
+}
+
+fn translate_touch_phase(phase: TouchPhase) -> rtk::event::TouchPhase {
+    use rtk::event::TouchPhase::*;
+
+    match phase {
+        TouchPhase::Started => Started,
+        TouchPhase::Moved => Moved,
+        TouchPhase::Ended => Ended,
+        TouchPhase::Cancelled => Cancelled,
+    }
+}
+
 fn translate_button(button: MouseButton) -> rtk::event::MouseButton {
     use rtk::event::MouseButton::*;
 
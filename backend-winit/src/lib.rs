@@ -1,3 +1,5 @@
+mod clipboard;
+pub use clipboard::*;
 mod event;
 pub use event::*;
 mod mainloop;
@@ -1,6 +1,9 @@
 use crate::event::translate_event;
+use rtk::timer;
+use rtk::widget::WidgetId;
 use std::collections::HashMap;
 use std::ops::Deref;
+use std::time::Instant;
 use winit::event::WindowEvent;
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::WindowId;
@@ -10,7 +13,12 @@ pub trait BackendWindow<R> {
     fn update(&mut self, resources: &mut R);
     fn draw(&mut self, resources: &mut R);
     fn request_redraw(&self);
+    fn scale_factor(&self) -> f64;
     fn push_event(&mut self, event: rtk::event::Event) -> bool;
+    #[allow(unused_variables)]
+    fn push_event_to(&mut self, target: WidgetId, event: rtk::event::Event) -> bool {
+        false
+    }
 }
 
 #[derive(Debug)]
@@ -47,8 +55,6 @@ impl<T: BackendWindow<R> + 'static, R: 'static> MainLoop<T, R> {
         let mut resources = self.resources;
 
         self.event_loop.run(move |event, _, cf| {
-            *cf = ControlFlow::Wait;
-
             match event {
                 Event::WindowEvent { event, window_id } => {
                     if let Some(window) = window_map.get_mut(&window_id) {
@@ -61,7 +67,8 @@ impl<T: BackendWindow<R> + 'static, R: 'static> MainLoop<T, R> {
                             | WindowEvent::ThemeChanged(_)
                         );
 
-                        let ev_consumed = translate_event(event).map_or(false, |ev| window.push_event(ev));
+                        let event = translate_event(event, window.scale_factor());
+                        let ev_consumed = event.map_or(false, |ev| window.push_event(ev));
                         if window_changed || ev_consumed {
                             // event was consumed, update and trigger a redraw
                             window.update(&mut resources);
@@ -74,10 +81,27 @@ impl<T: BackendWindow<R> + 'static, R: 'static> MainLoop<T, R> {
                     }
                 }
                 Event::MainEventsCleared => {
-                    if window_map.is_empty() {
-                        // no windows left, close the application
-                        *cf = ControlFlow::Exit;
+                    // deliver expired timers and animation frames
+                    for (target, event) in timer::take_expired(Instant::now()) {
+                        // we don't know which window has the target, so try all of them
+                        for window in window_map.values_mut() {
+                            if window.push_event_to(target, event.clone()) {
+                                window.update(&mut resources);
+                                window.request_redraw();
+                                break;
+                            }
+                        }
                     }
+
+                    *cf = if window_map.is_empty() {
+                        // no windows left, close the application
+                        ControlFlow::Exit
+                    } else if let Some(deadline) = timer::next_deadline() {
+                        // wake up for the next timer, or the next animation frame
+                        ControlFlow::WaitUntil(deadline)
+                    } else {
+                        ControlFlow::Wait
+                    };
                 }
                 Event::RedrawRequested(window_id) => {
                     if let Some(window) = window_map.get_mut(&window_id) {
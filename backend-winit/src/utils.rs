@@ -1,5 +1,6 @@
+use rtk::geometry::Rect;
 use rtk::toplevel::WindowAttributes;
-use winit::dpi::PhysicalSize;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::window::WindowBuilder;
 
 pub fn make_win_builder(win_attr: &WindowAttributes) -> WindowBuilder {
@@ -21,3 +22,9 @@ pub fn make_win_builder(win_attr: &WindowAttributes) -> WindowBuilder {
 
     win_builder
 }
+
+/// Gets the position of the input method candidate window for a caret area, so it doesn't cover the caret.
+#[inline]
+pub fn ime_position(area: Rect) -> PhysicalPosition<i32> {
+    PhysicalPosition::new(area.pos.x, area.pos.y + area.size.h as i32)
+}
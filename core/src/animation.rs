@@ -0,0 +1,310 @@
+//! Property animation and easing curves.
+//!
+//! Animated values are advanced by `AnimationFrame` events. While an animation is running it keeps
+//! requesting new frames (see `rtk::timer::request_animation_frame`), so the backend keeps
+//! redrawing the window until the value settles.
+use crate::draw::{Color, ColorOp};
+use crate::geometry::{Point, Rect, Size};
+use crate::timer;
+use crate::widget::WidgetId;
+use std::time::Duration;
+
+/// A value that can be interpolated between two endpoints.
+pub trait Interpolate: Clone {
+    /// Computes the value at `t` between `self` (at `t = 0`) and `other` (at `t = 1`).
+    ///
+    /// Values of `t` outside the `[0, 1]` range are allowed, so easing curves can overshoot.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t as f64
+    }
+}
+
+impl Interpolate for i32 {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (*self as f32).interpolate(&(*other as f32), t).round() as i32
+    }
+}
+
+impl Interpolate for u32 {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (*self as f32).interpolate(&(*other as f32), t).round() as u32
+    }
+}
+
+impl<T: Interpolate> Interpolate for Point<T> {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Point {
+            x: self.x.interpolate(&other.x, t),
+            y: self.y.interpolate(&other.y, t),
+        }
+    }
+}
+
+impl Interpolate for Size {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Size {
+            w: self.w.interpolate(&other.w, t),
+            h: self.h.interpolate(&other.h, t),
+        }
+    }
+}
+
+impl Interpolate for Rect {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Rect {
+            pos: self.pos.interpolate(&other.pos, t),
+            size: self.size.interpolate(&other.size, t),
+        }
+    }
+}
+
+impl Interpolate for Color {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        *self * (1.0 - t) + *other * t
+    }
+}
+
+impl Interpolate for ColorOp {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        ColorOp {
+            mul: self.mul.interpolate(&other.mul, t),
+            add: self.add.interpolate(&other.add, t),
+        }
+    }
+}
+
+/// Easing curve that maps the animation progress to the interpolation factor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// CSS-like cubic bezier curve with control points `(x1, y1)` and `(x2, y2)`.
+    ///
+    /// The `x` coordinates must be in the `[0, 1]` range.
+    CubicBezier(f32, f32, f32, f32),
+    /// Damped oscillation that overshoots the target before settling.
+    Spring {
+        /// Exponential decay rate of the oscillation over the animation duration.
+        damping: f32,
+        /// Number of oscillations over the animation duration.
+        frequency: f32,
+    },
+}
+
+impl Easing {
+    pub const EASE: Easing = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN: Easing = Easing::CubicBezier(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: Easing = Easing::CubicBezier(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: Easing = Easing::CubicBezier(0.42, 0.0, 0.58, 1.0);
+    pub const SPRING: Easing = Easing::Spring {
+        damping: 6.0,
+        frequency: 1.5,
+    };
+
+    /// Computes the interpolation factor for the specified progress in the `[0, 1]` range.
+    ///
+    /// The curve always starts at 0 and ends at 1.
+    pub fn apply(self, t: f32) -> f32 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        if t >= 1.0 {
+            return 1.0;
+        }
+        match self {
+            Easing::Linear => t,
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+            Easing::Spring { damping, frequency } => 1.0 - (-damping * t).exp() * (2.0 * std::f32::consts::PI * frequency * t).cos(),
+        }
+    }
+}
+
+impl Default for Easing {
+    #[inline]
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+const BEZIER_EPSILON: f32 = 1e-6;
+
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+    let bezier = |a: f32, b: f32, s: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+    };
+    let slope = |a: f32, b: f32, s: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * a + 6.0 * u * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    };
+
+    // find the curve parameter for `t` using newton's method, falling back to bisection
+    let mut s = t;
+    for _ in 0..8 {
+        let err = bezier(x1, x2, s) - t;
+        if err.abs() < BEZIER_EPSILON {
+            return bezier(y1, y2, s);
+        }
+        let d = slope(x1, x2, s);
+        if d.abs() < BEZIER_EPSILON {
+            break;
+        }
+        s -= err / d;
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = t;
+    while hi - lo > BEZIER_EPSILON {
+        let x = bezier(x1, x2, s);
+        if (x - t).abs() < BEZIER_EPSILON {
+            break;
+        }
+        if x < t {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+/// A value that transitions smoothly when it changes.
+///
+/// The owner widget must forward the `AnimationFrame` events it receives to `tick`.
+#[derive(Debug, Clone)]
+pub struct Animated<T> {
+    from: T,
+    to: T,
+    value: T,
+    elapsed: Duration,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl<T: Interpolate> Animated<T> {
+    /// Creates a settled value with the specified transition duration and easing.
+    #[inline]
+    pub fn new(value: T, duration: Duration, easing: Easing) -> Self {
+        Animated {
+            from: value.clone(),
+            to: value.clone(),
+            value,
+            elapsed: duration,
+            duration,
+            easing,
+        }
+    }
+
+    /// Gets the current value.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Gets the value the animation is heading to.
+    #[inline]
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// Checks if the value is still transitioning.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// Gets the transition duration.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Gets the easing curve.
+    #[inline]
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Changes the transition duration and easing curve.
+    #[inline]
+    pub fn set_transition(&mut self, duration: Duration, easing: Easing) {
+        self.duration = duration;
+        self.easing = easing;
+    }
+
+    /// Sets the value immediately, stopping the current transition.
+    pub fn set(&mut self, value: T) {
+        self.from = value.clone();
+        self.to = value.clone();
+        self.value = value;
+        self.elapsed = self.duration;
+    }
+
+    /// Starts a transition from the current value to the target value.
+    ///
+    /// Requests an animation frame for the owner widget to start the animation.
+    pub fn animate_to(&mut self, target: T, owner: WidgetId) {
+        if self.duration == Duration::default() {
+            self.set(target);
+            return;
+        }
+        self.from = self.value.clone();
+        self.to = target;
+        self.elapsed = Duration::default();
+        timer::request_animation_frame(owner);
+    }
+
+    /// Advances the animation time. Returns `true` if the transition is still running.
+    pub fn advance(&mut self, elapsed: Duration) -> bool {
+        if !self.is_running() {
+            return false;
+        }
+        self.elapsed = (self.elapsed + elapsed).min(self.duration);
+        if self.is_running() {
+            let t = self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+            self.value = self.from.interpolate(&self.to, self.easing.apply(t));
+            true
+        } else {
+            self.value = self.to.clone();
+            false
+        }
+    }
+
+    /// Handles an animation frame, requesting the next frame for the owner if the transition is still running.
+    ///
+    /// Returns `true` if the value changed, so the owner should consume the event to get redrawn.
+    pub fn tick(&mut self, elapsed: Duration, owner: WidgetId) -> bool {
+        let running = self.is_running();
+        if self.advance(elapsed) {
+            timer::request_animation_frame(owner);
+        }
+        running
+    }
+}
+
+impl<T: Interpolate + Default> Default for Animated<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Default::default(), Default::default(), Default::default())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::draw::{ColorOp, FillMode, TexCoord, TextSection, TextureId};
+use crate::draw::{ColorOp, FillMode, GlyphPosition, TexCoord, TextSection, TextureId};
 use crate::font::{FontFamily, FontId, FontLoadError, FontProperties, FontSource};
 use crate::geometry::{Point, Rect};
 use crate::image::Image;
@@ -36,6 +36,36 @@ pub trait Resources {
     /// The result of this method is cached, so a single font is loaded only once.
     fn load_font(&mut self, font_src: &FontSource) -> Result<FontId, FontLoadError>;
 
+    /// Measures the area that a text section covers when drawn.
+    ///
+    /// Returns `None` if the section doesn't produce any glyphs.
+    /// The default implementation merges the layout boxes from `glyph_positions`.
+    #[inline]
+    fn measure_text(&mut self, text: &TextSection) -> Option<Rect> {
+        self.glyph_positions(text)
+            .iter()
+            .map(GlyphPosition::bounds)
+            .reduce(Rect::merge)
+    }
+
+    /// Lays out a text section and returns the position of every glyph.
+    ///
+    /// Useful for placing a caret or finding the character under a point.
+    /// The default implementation doesn't support text layout and returns no glyphs.
+    #[allow(unused_variables)]
+    #[inline]
+    fn glyph_positions(&mut self, text: &TextSection) -> Vec<GlyphPosition> {
+        vec![]
+    }
+
+    /// Gets the clipboard provided by the backend.
+    ///
+    /// The default implementation doesn't provide a clipboard and returns `None`.
+    #[inline]
+    fn clipboard(&mut self) -> Option<&mut dyn Clipboard> {
+        None
+    }
+
     /// Creates a texture from an image.
     #[inline]
     fn create_texture(&mut self, image: &Image) -> Result<TextureId, TextureError> {
@@ -84,6 +114,53 @@ pub trait DrawBackend: Resources {
     }
 }
 
+/// Access to the clipboard provided by the backend.
+///
+/// Widgets reach it with `Resources::clipboard` (see `rtk::clipboard::ClipboardRequest`).
+pub trait Clipboard {
+    /// Gets the text stored in the clipboard.
+    ///
+    /// Returns `None` if the clipboard is empty or doesn't contain text.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Stores text in the clipboard.
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError>;
+
+    /// Gets the image stored in the clipboard.
+    ///
+    /// The default implementation returns `None`.
+    fn get_image(&mut self) -> Option<Image> {
+        None
+    }
+
+    /// Stores an image in the clipboard.
+    ///
+    /// The default implementation returns `ClipboardError::FormatNotSupported`.
+    #[allow(unused_variables)]
+    fn set_image(&mut self, image: &Image) -> Result<(), ClipboardError> {
+        Err(ClipboardError::FormatNotSupported)
+    }
+}
+
+/// Error produced by clipboard operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClipboardError {
+    /// The clipboard can't be accessed.
+    Unavailable,
+    /// The clipboard doesn't support the data format.
+    FormatNotSupported,
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let desc = match *self {
+            ClipboardError::Unavailable => "The clipboard is not available",
+            ClipboardError::FormatNotSupported => "The data format is not supported by the clipboard",
+        };
+        fmt.write_str(desc)
+    }
+}
+
 /// Error produced by texture operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TextureError {
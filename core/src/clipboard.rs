@@ -0,0 +1,72 @@
+//! Clipboard helpers for widgets.
+//!
+//! The clipboard is provided by the backend with `Resources::clipboard`.
+use crate::backend::{Clipboard, ClipboardError, Resources};
+use crate::image::Image;
+
+/// Clipboard that stores it's contents in memory.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MemoryClipboard {
+    pub content: Option<ClipboardContent>,
+}
+
+/// Data stored in a `MemoryClipboard`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardContent {
+    Text(String),
+    Image(Image),
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        match &self.content {
+            Some(ClipboardContent::Text(text)) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.content = Some(ClipboardContent::Text(text.into()));
+        Ok(())
+    }
+
+    fn get_image(&mut self) -> Option<Image> {
+        match &self.content {
+            Some(ClipboardContent::Image(image)) => Some(image.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_image(&mut self, image: &Image) -> Result<(), ClipboardError> {
+        self.content = Some(ClipboardContent::Image(image.clone()));
+        Ok(())
+    }
+}
+
+/// Clipboard operation requested by a widget.
+///
+/// Widgets don't receive the resources when handling events, so they store the request and
+/// perform it on the next layout update with `ClipboardRequest::apply`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardRequest {
+    /// Stores the text in the clipboard.
+    Copy(String),
+    /// Gets the text stored in the clipboard.
+    Paste,
+}
+
+impl ClipboardRequest {
+    /// Performs the operation on the clipboard provided by the resources.
+    ///
+    /// Returns the pasted text, or `None` if there is nothing to paste or the clipboard isn't available.
+    pub fn apply<R: Resources>(self, resources: &mut R) -> Option<String> {
+        let clipboard = resources.clipboard()?;
+        match self {
+            ClipboardRequest::Copy(text) => {
+                let _ = clipboard.set_text(&text);
+                None
+            }
+            ClipboardRequest::Paste => clipboard.get_text(),
+        }
+    }
+}
@@ -448,7 +448,7 @@ impl ops::Add<Color> for ColorOp {
 }
 
 #[inline]
-fn srgb_to_linear(s: f32) -> f32 {
+pub(crate) fn srgb_to_linear(s: f32) -> f32 {
     if s <= 0.04045 {
         s / 12.92
     } else {
@@ -457,7 +457,7 @@ fn srgb_to_linear(s: f32) -> f32 {
 }
 
 #[inline]
-fn linear_to_srgb(l: f32) -> f32 {
+pub(crate) fn linear_to_srgb(l: f32) -> f32 {
     if l <= 0.0031308 {
         l * 12.92
     } else {
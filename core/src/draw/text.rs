@@ -1,3 +1,45 @@
 pub use glyph_brush::Layout as TextLayout;
 pub use glyph_brush::Section as TextSection;
 pub use glyph_brush::Text;
+
+use crate::geometry::{Point, Rect};
+use glyph_brush::ab_glyph::Font;
+use glyph_brush::SectionGlyph;
+
+/// Position of a single glyph inside a laid out text section.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphPosition {
+    /// Index of the `Text` inside the section that produced this glyph.
+    pub section_index: usize,
+    /// Byte index of the glyph's character inside it's text.
+    pub byte_index: usize,
+    /// Top left corner of the glyph's layout box.
+    pub pos: Point<f32>,
+    /// Size of the glyph's layout box (horizontal advance and line height).
+    pub size: Point<f32>,
+}
+
+impl GlyphPosition {
+    /// Creates a glyph position from a glyph laid out by the text engine.
+    pub fn from_section_glyph(glyph: &SectionGlyph, font: &impl Font) -> Self {
+        let bounds = font.glyph_bounds(&glyph.glyph);
+        GlyphPosition {
+            section_index: glyph.section_index,
+            byte_index: glyph.byte_index,
+            pos: Point::new(bounds.min.x, bounds.min.y),
+            size: Point::new(bounds.width(), bounds.height()),
+        }
+    }
+
+    /// Gets the pixel area covered by the glyph's layout box.
+    #[inline]
+    pub fn bounds(&self) -> Rect {
+        Rect::enclosing(self.pos, self.pos + self.size)
+    }
+
+    /// Gets the horizontal position where the glyph ends.
+    #[inline]
+    pub fn end_x(&self) -> f32 {
+        self.pos.x + self.size.x
+    }
+}
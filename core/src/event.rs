@@ -1,17 +1,23 @@
 //! Input event definitions.
 mod dispatcher;
 pub use dispatcher::*;
+mod gesture;
+pub use gesture::*;
 
 use crate::geometry::{Point, Position, Size};
+use crate::timer::TimerId;
 use crate::widget::WidgetId;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub use ButtonState::*;
 
 /// Raw key id from hardware.
 pub type ScanCode = u32;
 
+/// Identifier of a touch contact point.
+pub type TouchId = u64;
+
 /// Input events that come from the backend.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Event {
@@ -19,6 +25,20 @@ pub enum Event {
     Keyboard { state: ButtonState, key: Key, scancode: ScanCode },
     /// Processed keyboard input as an unicode character.
     Character(char),
+    /// Input method composition text changed.
+    ///
+    /// The text is shown at the caret until it's committed. An empty text ends the composition.
+    ///
+    /// Not generated by the winit backend yet: winit 0.22 doesn't report the composition.
+    ImePreedit {
+        text: String,
+        /// Byte range of the composition cursor inside the text, `None` hides the cursor.
+        cursor: Option<(usize, usize)>,
+    },
+    /// Input method composition finished, the text should be inserted at the caret.
+    ///
+    /// Not generated by the winit backend yet, the committed text arrives as `Character` events instead.
+    ImeCommit(String),
     /// Keyboard modifier state changed.
     ModifiersChanged(KeyModState),
     /// Mouse pointer motion.
@@ -27,6 +47,33 @@ pub enum Event {
     MouseButton(ButtonState, MouseButton),
     /// Pointer has crossed the window boundaries.
     PointerInside(bool),
+    /// Touch screen contact.
+    ///
+    /// Every contact point has an unique id while it's touching the screen.
+    Touch {
+        id: TouchId,
+        phase: TouchPhase,
+        /// Position of the contact point, relative to the window.
+        pos: Point<f64>,
+        /// Normalized pressure of the contact in the 0-1 range, if the device reports it.
+        force: Option<f64>,
+    },
+    /// The widget has received keyboard focus.
+    FocusGained,
+    /// The widget has lost keyboard focus.
+    FocusLost,
+    /// A mouse button has been pressed and released over the widget.
+    Click(MouseButton),
+    /// A mouse button has been clicked twice in a short interval.
+    DoubleClick(MouseButton),
+    /// A mouse button has been held down on the widget for a while. It's sent while the button is still down.
+    LongPress(MouseButton),
+    /// A gesture has been recognized from pointer or touch input.
+    Gesture(Gesture),
+    /// A timer has expired.
+    Timer(TimerId),
+    /// Time to update an animation. Contains the time elapsed since the frame was requested.
+    AnimationFrame(Duration),
     /// A file has been dropped into the window.
     FileDropped(PathBuf),
     /// Window resized.
@@ -48,9 +95,9 @@ pub enum Event {
 pub struct EventContext {
     /// Instant when the event was received.
     pub timestamp: Instant,
-    /// Last known cursor position, relative to the widget.
+    /// Last known cursor position (or the contact position on touch events), relative to the widget.
     pub local_pos: Point<f64>,
-    /// Last known cursor position, relative to the window.
+    /// Last known cursor position (or the contact position on touch events), relative to the window.
     pub abs_pos: Point<f64>,
     /// Current mouse button state.
     pub button_state: MouseButtonsState,
@@ -60,6 +107,8 @@ pub struct EventContext {
     pub widget: WidgetId,
     /// The receiver widget's parent.
     pub parent: WidgetId,
+    /// The dispatch phase this event is being delivered in.
+    pub phase: EventPhase,
 }
 
 impl EventContext {
@@ -74,6 +123,29 @@ impl EventContext {
     }
 }
 
+/// Phase of the event dispatch.
+///
+/// Pointer events and keyboard events sent to the focused widget travel through the widget tree
+/// in three phases: first they go down from the root to the target's parent (`Capture`), then to the
+/// target itself (`Target`), and finally back up through the target's ancestors (`Bubble`).
+/// Events that aren't sent to a specific target are always delivered in the `Target` phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventPhase {
+    /// The event is going down to the target. Delivered by `Widget::capture_event`.
+    Capture,
+    /// The event has reached the target.
+    Target,
+    /// The event is going up from the target to the root.
+    Bubble,
+}
+
+impl Default for EventPhase {
+    #[inline]
+    fn default() -> Self {
+        EventPhase::Target
+    }
+}
+
 /// State of keys or mouse buttons.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ButtonState {
@@ -125,11 +197,64 @@ impl MouseButton {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Axis {
     Position(Point<f64>),
-    Scroll(f32, f32),
+    Scroll(ScrollDelta, ScrollPhase),
     Pressure(f64),
     Tilt(f64, f64),
 }
 
+/// Amount of movement of a scroll event, on both axes.
+///
+/// Positive values scroll the contents right/down (moving the view towards the start).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollDelta {
+    /// Lines (or rows/columns) to scroll, as reported by mouse wheels.
+    Lines(f32, f32),
+    /// Exact amount of pixels to scroll, as reported by touchpads and other precise devices.
+    Pixels(f64, f64),
+}
+
+impl ScrollDelta {
+    /// Converts the delta into pixels, using the specified size for a line.
+    #[inline]
+    pub fn to_pixels(self, line_size: f64) -> Point<f64> {
+        match self {
+            ScrollDelta::Lines(x, y) => Point::new(x as f64 * line_size, y as f64 * line_size),
+            ScrollDelta::Pixels(x, y) => Point::new(x, y),
+        }
+    }
+}
+
+/// Stage of a scroll gesture.
+///
+/// Devices without gestures (like mouse wheels) only produce `Moved` events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollPhase {
+    Started,
+    Moved,
+    Ended,
+}
+
+/// Stage of a touch contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    /// The finger touched the screen.
+    Started,
+    /// The contact point moved.
+    Moved,
+    /// The finger was lifted from the screen.
+    Ended,
+    /// The system cancelled the touch (the contact is no longer tracked).
+    Cancelled,
+}
+
+impl TouchPhase {
+    /// Checks if this is the last event of a contact.
+    #[inline]
+    pub fn is_end(self) -> bool {
+        matches!(self, TouchPhase::Ended | TouchPhase::Cancelled)
+    }
+}
+
 /// Keyboard modifier state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct KeyModState {
@@ -162,6 +287,12 @@ impl MouseButtonsState {
         self.0 & button.mask() != 0
     }
 
+    /// Checks if any button is pressed.
+    #[inline]
+    pub fn any(self) -> bool {
+        self.0 != 0
+    }
+
     /// Checks if the left button is pressed.
     #[inline]
     pub fn left(self) -> bool {
@@ -263,6 +394,12 @@ pub enum EventResult {
     ConsumedNotifyBroadcast,
     /// Event consumed. Calls `Widget::event_consumed` on a specific widget.
     ConsumedNotifyTarget(WidgetId),
+    /// Event consumed. The widget requests keyboard focus.
+    ConsumedFocus,
+    /// Event consumed. The widget grabs the pointer until all mouse buttons are released.
+    ConsumedGrab,
+    /// Event consumed. The widget releases the pointer grab.
+    ConsumedReleaseGrab,
 }
 
 impl EventResult {
@@ -272,6 +409,9 @@ impl EventResult {
             EventResult::Consumed
             | EventResult::ConsumedNotifyBroadcast
             | EventResult::ConsumedNotifyTarget(_)
+            | EventResult::ConsumedFocus
+            | EventResult::ConsumedGrab
+            | EventResult::ConsumedReleaseGrab
         )
     }
 }
@@ -1,7 +1,12 @@
-use crate::event::{Axis, ButtonState, Event, EventContext, EventResult, KeyModState, MouseButtonsState};
+use crate::event::{
+    Axis, ButtonState, Event, EventContext, EventPhase, EventResult, Gesture, GesturePhase, GestureRecognizer, GestureSettings, Key,
+    KeyModState, MouseButton, MouseButtonsState, ScrollPhase, TouchId, TouchPhase,
+};
 use crate::geometry::{Point, Position, Rect, Size};
+use crate::timer::{self, TimerId};
 use crate::visitor::Visitor;
 use crate::widget::{Widget, WidgetId};
+use std::time::{Duration, Instant};
 
 /// Sends an event to all widgets (until consumed).
 struct EventDispatchVisitor {
@@ -35,40 +40,6 @@ impl Visitor for EventDispatchVisitor {
     }
 }
 
-/// Sends an event to the widget under cursor.
-struct PositionDispatchVisitor {
-    event: Event,
-    ctx: EventContext,
-    consumed: bool,
-    ev_res: EventResult,
-}
-
-impl Visitor for PositionDispatchVisitor {
-    type Context = BoundsContext;
-
-    fn visit_after<W: Widget>(mut self, widget: &mut W, this: &Self::Context) -> Self {
-        if self.ctx.abs_pos.inside(this.abs_bounds) {
-            let ctx = self.ctx.update(this.abs_bounds.pos.cast(), this.id, this.parent_id);
-            let ev_res = widget.handle_event(&self.event, ctx);
-            if ev_res.consumed() {
-                self.ctx = ctx;
-                self.consumed = true;
-                self.ev_res = ev_res;
-            }
-        }
-        self
-    }
-
-    fn new_context<W: Widget>(&self, widget: &W, parent_ctx: &Self::Context) -> Option<Self::Context> {
-        BoundsContext::from_parent(parent_ctx, widget)
-    }
-
-    #[inline]
-    fn finished(&self) -> bool {
-        self.consumed
-    }
-}
-
 /// Checks what widget is under the cursor.
 struct InsideCheckVisitor {
     ctx: EventContext,
@@ -143,6 +114,160 @@ impl Visitor for TargetedDispatchVisitor {
     }
 }
 
+/// Finds the path from the root to the target of a phased dispatch.
+struct PathFindVisitor {
+    target: PathTarget,
+    index: usize,
+    stack: Vec<usize>,
+    path: Option<Vec<usize>>,
+}
+
+impl Visitor for PathFindVisitor {
+    type Context = PathContext;
+
+    fn visit_before<W: Widget>(mut self, _: &mut W, this: &Self::Context) -> Self {
+        self.index += 1;
+        self.stack.push(this.index);
+        if self.target == PathTarget::Widget(this.id) {
+            self.path = Some(self.stack.clone());
+        }
+        self
+    }
+
+    fn visit_after<W: Widget>(mut self, _: &mut W, _: &Self::Context) -> Self {
+        // the first widget that finishes under the pointer is the top-most one
+        if let PathTarget::Position(_) = self.target {
+            self.path = Some(self.stack.clone());
+        }
+        self.stack.pop();
+        self
+    }
+
+    fn new_context<W: Widget>(&self, widget: &W, parent_ctx: &Self::Context) -> Option<Self::Context> {
+        PathContext::from_parent(parent_ctx, widget, self.target, self.index)
+    }
+
+    #[inline]
+    fn finished(&self) -> bool {
+        self.path.is_some()
+    }
+}
+
+/// Sends an event along a path: capture on the ancestors, then the target, then bubble up.
+struct PhaseDispatchVisitor {
+    target: PathTarget,
+    path: Vec<usize>,
+    index: usize,
+    reached: bool,
+    event: Event,
+    ctx: EventContext,
+    consumed: bool,
+    ev_res: EventResult,
+}
+
+impl PhaseDispatchVisitor {
+    fn dispatch<W: Widget>(&mut self, widget: &mut W, this: &PathContext, phase: EventPhase) {
+        let ctx = EventContext {
+            phase,
+            ..self.ctx.update(this.abs_pos.cast(), this.id, this.parent_id)
+        };
+        let ev_res = if phase == EventPhase::Capture {
+            widget.capture_event(&self.event, ctx)
+        } else {
+            widget.handle_event(&self.event, ctx)
+        };
+        if ev_res.consumed() {
+            self.ctx = ctx;
+            self.consumed = true;
+            self.ev_res = ev_res;
+        }
+    }
+}
+
+impl Visitor for PhaseDispatchVisitor {
+    type Context = PathContext;
+
+    fn visit_before<W: Widget>(mut self, widget: &mut W, this: &Self::Context) -> Self {
+        self.index += 1;
+        if self.path.last() == Some(&this.index) {
+            self.reached = true;
+            self.dispatch(widget, this, EventPhase::Target);
+        } else if self.path.contains(&this.index) {
+            self.dispatch(widget, this, EventPhase::Capture);
+        }
+        self
+    }
+
+    fn visit_after<W: Widget>(mut self, widget: &mut W, this: &Self::Context) -> Self {
+        // after the target is reached, only it's ancestors are visited
+        if self.reached && self.path.last() != Some(&this.index) {
+            self.dispatch(widget, this, EventPhase::Bubble);
+        }
+        self
+    }
+
+    fn new_context<W: Widget>(&self, widget: &W, parent_ctx: &Self::Context) -> Option<Self::Context> {
+        if self.reached {
+            return None;
+        }
+        PathContext::from_parent(parent_ctx, widget, self.target, self.index)
+    }
+
+    #[inline]
+    fn finished(&self) -> bool {
+        self.consumed
+    }
+}
+
+/// Gets the input method cursor area of a widget.
+struct ImeAreaVisitor {
+    target: WidgetId,
+    area: Option<Rect>,
+    found: bool,
+}
+
+impl Visitor for ImeAreaVisitor {
+    type Context = PosContext;
+
+    fn visit_before<W: Widget>(mut self, widget: &mut W, this: &Self::Context) -> Self {
+        if self.target == this.id {
+            self.area = widget.ime_cursor_area().map(|area| area.offset(this.abs_pos));
+            self.found = true;
+        }
+        self
+    }
+
+    fn new_context<W: Widget>(&self, widget: &W, parent_ctx: &Self::Context) -> Option<Self::Context> {
+        PosContext::from_parent(parent_ctx, widget)
+    }
+
+    #[inline]
+    fn finished(&self) -> bool {
+        self.found
+    }
+}
+
+/// Collects the widgets that accept keyboard focus, in tree order.
+struct FocusListVisitor {
+    list: Vec<(u32, WidgetId)>,
+}
+
+impl Visitor for FocusListVisitor {
+    type Context = ();
+
+    fn visit_before<W: Widget>(mut self, widget: &mut W, _: &Self::Context) -> Self {
+        let id = widget.get_id();
+        if id != WidgetId::NONE && widget.accepts_focus() {
+            self.list.push((widget.tab_index(), id));
+        }
+        self
+    }
+
+    fn new_context<W: Widget>(&self, _: &W, _: &Self::Context) -> Option<Self::Context> {
+        Some(())
+    }
+}
+
 /// Sends an event consumed notification to every widget in the tree.
 struct BroadcastNotifyVisitor {
     event: Event,
@@ -204,19 +329,128 @@ fn notify_consumed<W: Widget>(root: &mut W, ev_res: EventResult, event: Event, c
     }
 }
 
+/// Sends an event to a single widget and notifies the result.
+fn dispatch_targeted<W: Widget>(root: &mut W, target: WidgetId, event: Event, ctx: EventContext) -> bool {
+    let visitor = TargetedDispatchVisitor {
+        target,
+        event,
+        ctx,
+        consumed: false,
+        ev_res: EventResult::Pass,
+    };
+    let result = root.accept(visitor, &Default::default());
+    notify_consumed(root, result.ev_res, result.event, result.ctx);
+    result.consumed
+}
+
+/// Sends an event to a target in the capture, target and bubble phases.
+///
+/// Returns `None` if the target wasn't found.
+fn dispatch_phased<W: Widget>(
+    root: &mut W, target: PathTarget, event: Event, ctx: EventContext, parent_size: Size,
+) -> Option<PhaseDispatchVisitor> {
+    let root_ctx = PathContext::from(parent_size);
+    let visitor = PathFindVisitor {
+        target,
+        index: 0,
+        stack: vec![],
+        path: None,
+    };
+    let path = root.accept(visitor, &root_ctx).path?;
+    let visitor = PhaseDispatchVisitor {
+        target,
+        path,
+        index: 0,
+        reached: false,
+        event,
+        ctx,
+        consumed: false,
+        ev_res: EventResult::Pass,
+    };
+    let result = root.accept(visitor, &root_ctx);
+    notify_consumed(root, result.ev_res, result.event.clone(), result.ctx);
+    Some(result)
+}
+
+/// Gets the widget that requested focus from an event result.
+#[inline]
+fn focus_request(ev_res: EventResult, ctx: EventContext) -> Option<WidgetId> {
+    if ev_res == EventResult::ConsumedFocus {
+        Some(ctx.widget)
+    } else {
+        None
+    }
+}
+
+/// Settings for the synthesized click events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClickSettings {
+    /// Maximum time between two clicks to be considered a double click.
+    pub double_click_interval: Duration,
+    /// Minimum time a button must be held to be considered a long press.
+    pub long_press_time: Duration,
+    /// Maximum distance the pointer can move from the press position.
+    pub distance: f64,
+}
+
+impl Default for ClickSettings {
+    #[inline]
+    fn default() -> Self {
+        ClickSettings {
+            double_click_interval: Duration::from_millis(500),
+            long_press_time: Duration::from_millis(500),
+            distance: 4.0,
+        }
+    }
+}
+
+/// A mouse button press delivered to a widget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ButtonPress {
+    button: MouseButton,
+    widget: WidgetId,
+    pos: Point<f64>,
+    timestamp: Instant,
+    /// Pending long press timer, `None` once the long press has been sent.
+    long_press: Option<TimerId>,
+}
+
 /// Helper to dispatch toplevel events into a widget tree.
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+///
+/// This isn't `Copy` since it keeps the active touch contacts, use `clone` to duplicate the dispatcher state.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct EventDispatcher {
     last_pos: Point<f64>,
     mod_state: KeyModState,
     button_state: MouseButtonsState,
     last_inside: Option<WidgetId>,
+    focused: Option<WidgetId>,
+    skip_tab_char: bool,
+    grab: Option<WidgetId>,
+    scroll_target: Option<WidgetId>,
+    /// Active touch contacts and the widget that receives them.
+    touches: Vec<(TouchId, WidgetId)>,
+    click_settings: ClickSettings,
+    press: Option<ButtonPress>,
+    last_click: Option<ButtonPress>,
+    gestures: GestureRecognizer,
+    gesture_settings: GestureSettings,
+    gesture_target: Option<WidgetId>,
 }
 
 impl EventDispatcher {
+    #[inline]
     pub fn dispatch_event<W: Widget>(&mut self, event: Event, parent_size: Size, root: &mut W) -> bool {
+        self.dispatch_event_at(event, Instant::now(), parent_size, root)
+    }
+
+    /// Dispatches an event that was received at the specified instant.
+    ///
+    /// The timestamp is used for the click timing, so a test driver can simulate the passage of time.
+    pub fn dispatch_event_at<W: Widget>(&mut self, event: Event, timestamp: Instant, parent_size: Size, root: &mut W) -> bool {
         self.update_state(&event);
-        let ctx = self.make_context();
+        let ctx = self.make_context(timestamp);
+        let gestures = self.gestures.update(&event, &ctx, &self.gesture_settings);
 
         // check if pointer inside/outside changed
         let mut in_res = false;
@@ -246,25 +480,64 @@ impl EventDispatcher {
         };
 
         // dispatch "outside changed" event
-        let out_res = outside_target.map_or(false, |target| {
-            let visitor = TargetedDispatchVisitor {
-                target,
-                event: Event::PointerInside(false),
-                ctx,
-                consumed: false,
-                ev_res: EventResult::Pass,
-            };
-            let result = root.accept(visitor, &Default::default());
-            notify_consumed(root, result.ev_res, Event::PointerInside(false), result.ctx);
-            result.consumed
-        });
+        let out_res = outside_target.map_or(false, |target| dispatch_targeted(root, target, Event::PointerInside(false), ctx));
+
+        // the Tab key that moved focus also produces a character, it shouldn't reach the new widget
+        match event {
+            Event::Character('\t') if self.skip_tab_char => {
+                self.skip_tab_char = false;
+                return in_res | out_res;
+            }
+            Event::Keyboard {
+                state: ButtonState::Pressed,
+                ..
+            } => self.skip_tab_char = false,
+            _ => (),
+        }
+
+        let is_tab_press = matches!(
+            event,
+            Event::Keyboard {
+                state: ButtonState::Pressed,
+                key: Key::Tab,
+                ..
+            }
+        );
+
+        let button_event = match event {
+            Event::MouseButton(state, button) => Some((state, button)),
+            _ => None,
+        };
+
+        // keyboard events go to the focused widget first
+        let focused_res = match event {
+            Event::Keyboard { .. } | Event::Character(_) | Event::ImePreedit { .. } | Event::ImeCommit(_) => {
+                let result = self
+                    .focused
+                    .and_then(|id| dispatch_phased(root, PathTarget::Widget(id), event.clone(), ctx, parent_size));
+                if result.is_none() {
+                    // the focused widget is gone, the event is broadcast like when nothing has focus
+                    self.focused = None;
+                }
+                result
+            }
+            _ => None,
+        };
 
         // dispatch other events
-        // TODO: keyboard focus, mouse grab
+        let mut widget_res = None;
+        let mut receiver = None;
         let ev_res = match event {
+            Event::Keyboard { .. } | Event::Character(_) | Event::ImePreedit { .. } | Event::ImeCommit(_) if focused_res.is_some() => {
+                let result = focused_res.unwrap();
+                widget_res = Some((result.ev_res, result.ctx));
+                result.consumed
+            }
             // position independant events
             Event::Keyboard { .. }
             | Event::Character(_)
+            | Event::ImePreedit { .. }
+            | Event::ImeCommit(_)
             | Event::ModifiersChanged(_)
             | Event::CloseRequest
             | Event::Resized(_)
@@ -279,26 +552,360 @@ impl EventDispatcher {
                     ev_res: EventResult::Pass,
                 };
                 let result = root.accept(visitor, &Default::default());
+                widget_res = Some((result.ev_res, result.ctx));
                 notify_consumed(root, result.ev_res, result.event, result.ctx);
                 result.consumed
             }
+            // scroll gestures stay on the widget that received the start of the gesture
+            Event::MouseMoved(Axis::Scroll(_, phase)) if self.scroll_target.is_some() => {
+                let target = PathTarget::Widget(self.scroll_target.unwrap());
+                if phase == ScrollPhase::Ended {
+                    self.scroll_target = None;
+                }
+                if let Some(result) = dispatch_phased(root, target, event, ctx, parent_size) {
+                    widget_res = Some((result.ev_res, result.ctx));
+                    result.consumed
+                } else {
+                    // the scrolled widget is gone
+                    self.scroll_target = None;
+                    false
+                }
+            }
+            // pointer events go to the widget that has grabbed the pointer
+            Event::MouseMoved(_) | Event::MouseButton(_, _)
+                if self.grab.is_some() && !matches!(event, Event::MouseMoved(Axis::Scroll(..))) =>
+            {
+                let target = PathTarget::Widget(self.grab.unwrap());
+                if let Some(result) = dispatch_phased(root, target, event, ctx, parent_size) {
+                    receiver = self.grab;
+                    // an ancestor that captures the event takes over the grab
+                    if result.consumed && result.ctx.phase == EventPhase::Capture && result.ctx.widget != WidgetId::NONE {
+                        self.grab = Some(result.ctx.widget);
+                        receiver = self.grab;
+                    }
+                    widget_res = Some((result.ev_res, result.ctx));
+                    result.consumed
+                } else {
+                    // the grabbing widget is gone
+                    self.grab = None;
+                    false
+                }
+            }
+            // touch contacts go to the widget that consumed the start of the contact
+            Event::Touch { id, phase, pos, .. } => {
+                let tracked = self
+                    .touches
+                    .iter()
+                    .position(|&(touch, _)| touch == id)
+                    .map(|i| self.touches.remove(i).1);
+                let target = match phase {
+                    TouchPhase::Started => Some(PathTarget::Position(pos)),
+                    _ => tracked.map(PathTarget::Widget),
+                };
+                let ctx = EventContext {
+                    local_pos: pos,
+                    abs_pos: pos,
+                    ..ctx
+                };
+                if let Some(result) = target.and_then(|target| dispatch_phased(root, target, event, ctx, parent_size)) {
+                    let widget = match phase {
+                        TouchPhase::Started if result.consumed => Some(result.ctx.widget),
+                        _ => tracked,
+                    };
+                    if let Some(widget) = widget.filter(|&w| w != WidgetId::NONE && !phase.is_end()) {
+                        self.touches.push((id, widget));
+                    }
+                    widget_res = Some((result.ev_res, result.ctx));
+                    result.consumed
+                } else {
+                    false
+                }
+            }
             // position dependant events
             Event::MouseMoved(_) | Event::MouseButton(_, _) | Event::FileDropped(_) => {
-                let visitor = PositionDispatchVisitor {
-                    event: event,
-                    ctx,
-                    consumed: false,
-                    ev_res: EventResult::Pass,
-                };
-                let result = root.accept(visitor, &parent_size.into());
-                notify_consumed(root, result.ev_res, result.event, result.ctx);
-                result.consumed
+                let target = PathTarget::Position(ctx.abs_pos);
+                if let Some(result) = dispatch_phased(root, target, event, ctx, parent_size) {
+                    if result.consumed && result.ctx.widget != WidgetId::NONE {
+                        receiver = Some(result.ctx.widget);
+                        match result.event {
+                            // the widget that consumes a button press grabs the pointer
+                            Event::MouseButton(ButtonState::Pressed, _) => self.grab = receiver,
+                            // and the one that consumes the start of a scroll gesture receives the rest of it
+                            Event::MouseMoved(Axis::Scroll(_, ScrollPhase::Started)) => self.scroll_target = receiver,
+                            _ => (),
+                        }
+                    }
+                    widget_res = Some((result.ev_res, result.ctx));
+                    result.consumed
+                } else {
+                    false
+                }
             }
             // already handled
             Event::PointerInside(_) => false,
+            // generated by the dispatcher
+            Event::FocusGained | Event::FocusLost | Event::Click(_) | Event::DoubleClick(_) | Event::LongPress(_) | Event::Gesture(_) => {
+                false
+            }
+            // only sent to a target
+            Event::Timer(_) | Event::AnimationFrame(_) => false,
+        };
+
+        // synthesize click events from press/release pairs received by the same widget
+        let click_res = match (button_event, receiver) {
+            (Some((ButtonState::Pressed, button)), Some(widget)) => {
+                self.cancel_press();
+                // the long press is sent by a timer while the button is held
+                let deadline = ctx.timestamp + self.click_settings.long_press_time;
+                self.press = Some(ButtonPress {
+                    button,
+                    widget,
+                    pos: ctx.abs_pos,
+                    timestamp: ctx.timestamp,
+                    long_press: Some(timer::set_timeout_at(widget, deadline)),
+                });
+                false
+            }
+            (Some((ButtonState::Released, button)), Some(widget)) => self.synthesize_click(button, widget, ctx, root),
+            (Some(_), None) => {
+                self.cancel_press();
+                false
+            }
+            _ => false,
         };
 
-        ev_res | in_res | out_res
+        let gesture_res = self.dispatch_gestures(gestures, ctx, parent_size, root);
+
+        // widgets can also take or release the grab explicitly
+        match widget_res {
+            Some((EventResult::ConsumedGrab, res_ctx)) if res_ctx.widget != WidgetId::NONE => self.grab = Some(res_ctx.widget),
+            Some((EventResult::ConsumedReleaseGrab, _)) => self.grab = None,
+            _ => (),
+        }
+
+        // the grab ends when all buttons are released
+        if matches!(button_event, Some((ButtonState::Released, _))) && !self.button_state.any() {
+            self.grab = None;
+        }
+
+        let focus_req = widget_res.and_then(|(ev_res, res_ctx)| focus_request(ev_res, res_ctx));
+        let focus_res = focus_req.is_some_and(|target| self.set_focus_ctx(Some(target), ctx, root));
+
+        // unhandled Tab key moves the focus
+        if is_tab_press && !ev_res && self.focus_next_ctx(self.mod_state.shift, ctx, root) {
+            self.skip_tab_char = true;
+            return true;
+        }
+
+        ev_res | in_res | out_res | focus_res | click_res | gesture_res
+    }
+
+    /// Sends the recognized gestures to the widget where they began.
+    fn dispatch_gestures<W: Widget>(
+        &mut self, gestures: Vec<(Gesture, Point<f64>)>, ctx: EventContext, parent_size: Size, root: &mut W,
+    ) -> bool {
+        let ends = gestures.last().is_some_and(|(gesture, _)| gesture.phase() == GesturePhase::Ended);
+        let mut consumed = false;
+        for (gesture, pos) in gestures {
+            let is_start = gesture.phase() == GesturePhase::Started;
+            let target = if is_start {
+                self.gesture_target = None;
+                Some(PathTarget::Position(self.gestures.origin()))
+            } else {
+                self.gesture_target.map(PathTarget::Widget)
+            };
+            let ctx = EventContext {
+                local_pos: pos,
+                abs_pos: pos,
+                ..ctx
+            };
+            if let Some(result) = target.and_then(|target| dispatch_phased(root, target, Event::Gesture(gesture), ctx, parent_size)) {
+                // the widget that consumes the start of a gesture receives the rest of it
+                if is_start && result.consumed && result.ctx.widget != WidgetId::NONE {
+                    self.gesture_target = Some(result.ctx.widget);
+                }
+                consumed |= result.consumed;
+            }
+        }
+        if ends {
+            self.gesture_target = None;
+        }
+        consumed
+    }
+
+    fn synthesize_click<W: Widget>(&mut self, button: MouseButton, widget: WidgetId, ctx: EventContext, root: &mut W) -> bool {
+        let press = self.press.filter(|press| press.button == button && press.widget == widget);
+        self.cancel_press();
+        let press = match press {
+            Some(press) if press.long_press.is_some() => press,
+            // the long press was sent while the button was held, so it's not a click
+            Some(_) => {
+                self.last_click = None;
+                return false;
+            }
+            None => return false,
+        };
+        let settings = self.click_settings;
+
+        let click_res = dispatch_targeted(root, widget, Event::Click(button), ctx);
+        let is_double = self.last_click.take().is_some_and(|last| {
+            last.button == button
+                && last.widget == widget
+                && ctx.timestamp.saturating_duration_since(last.timestamp) <= settings.double_click_interval
+                && last.pos.distance_to(ctx.abs_pos) <= settings.distance
+        });
+        if is_double {
+            click_res | dispatch_targeted(root, widget, Event::DoubleClick(button), ctx)
+        } else {
+            self.last_click = Some(ButtonPress {
+                timestamp: ctx.timestamp,
+                long_press: None,
+                ..press
+            });
+            click_res
+        }
+    }
+
+    /// Forgets the current button press, cancelling it's long press timer.
+    fn cancel_press(&mut self) {
+        if let Some(id) = self.press.take().and_then(|press| press.long_press) {
+            timer::cancel_timer(id);
+        }
+    }
+
+    /// Sends an event to a specific widget.
+    ///
+    /// The long press timer of the current button press is turned into a `LongPress` event here.
+    pub fn dispatch_to<W: Widget>(&mut self, target: WidgetId, event: Event, root: &mut W) -> bool {
+        if let Event::Timer(id) = event {
+            let long_press_time = self.click_settings.long_press_time;
+            if let Some(press) = self
+                .press
+                .as_mut()
+                .filter(|press| press.long_press == Some(id) && press.widget == target)
+            {
+                press.long_press = None;
+                let (button, timestamp) = (press.button, press.timestamp + long_press_time);
+                let ctx = self.make_context(timestamp);
+                self.last_click = None;
+                return dispatch_targeted(root, target, Event::LongPress(button), ctx);
+            }
+        }
+        let ctx = self.make_context(Instant::now());
+        dispatch_targeted(root, target, event, ctx)
+    }
+
+    /// Gets the settings used for synthesized click events.
+    #[inline]
+    pub fn click_settings(&self) -> &ClickSettings {
+        &self.click_settings
+    }
+
+    /// Sets the settings used for synthesized click events.
+    #[inline]
+    pub fn set_click_settings(&mut self, settings: ClickSettings) {
+        self.click_settings = settings;
+    }
+
+    /// Gets the settings used for gesture recognition.
+    #[inline]
+    pub fn gesture_settings(&self) -> &GestureSettings {
+        &self.gesture_settings
+    }
+
+    /// Sets the settings used for gesture recognition.
+    #[inline]
+    pub fn set_gesture_settings(&mut self, settings: GestureSettings) {
+        self.gesture_settings = settings;
+    }
+
+    /// Gets the widget that receives the current gesture.
+    #[inline]
+    pub fn gesture_target(&self) -> Option<WidgetId> {
+        self.gesture_target
+    }
+
+    /// Gets the widget that has keyboard focus.
+    #[inline]
+    pub fn focused(&self) -> Option<WidgetId> {
+        self.focused
+    }
+
+    /// Gets the input method cursor area of the focused widget, relative to the window.
+    ///
+    /// See `Widget::ime_cursor_area`.
+    pub fn ime_cursor_area<W: Widget>(&self, root: &mut W) -> Option<Rect> {
+        let visitor = ImeAreaVisitor {
+            target: self.focused?,
+            area: None,
+            found: false,
+        };
+        root.accept(visitor, &Default::default()).area
+    }
+
+    /// Gets the widget that has grabbed the pointer.
+    #[inline]
+    pub fn grabbed(&self) -> Option<WidgetId> {
+        self.grab
+    }
+
+    /// Gets the widget that receives the events of a touch contact.
+    #[inline]
+    pub fn touch_target(&self, id: TouchId) -> Option<WidgetId> {
+        self.touches.iter().find(|&&(touch, _)| touch == id).map(|&(_, widget)| widget)
+    }
+
+    /// Releases the pointer grab before all mouse buttons are released.
+    ///
+    /// Widgets can do the same by returning `EventResult::ConsumedReleaseGrab`.
+    #[inline]
+    pub fn release_grab(&mut self) {
+        self.grab = None;
+    }
+
+    /// Moves the keyboard focus to the specified widget, or removes focus if `None`.
+    ///
+    /// The `FocusLost` and `FocusGained` events are sent to the affected widgets.
+    pub fn set_focus<W: Widget>(&mut self, target: Option<WidgetId>, root: &mut W) -> bool {
+        let ctx = self.make_context(Instant::now());
+        self.set_focus_ctx(target, ctx, root)
+    }
+
+    /// Moves the keyboard focus to the next widget that accepts focus, or the previous one if `reverse` is set.
+    ///
+    /// Wraps around at the ends of the focus list.
+    pub fn focus_next<W: Widget>(&mut self, reverse: bool, root: &mut W) -> bool {
+        let ctx = self.make_context(Instant::now());
+        self.focus_next_ctx(reverse, ctx, root)
+    }
+
+    fn focus_next_ctx<W: Widget>(&mut self, reverse: bool, ctx: EventContext, root: &mut W) -> bool {
+        let mut list = root.accept(FocusListVisitor { list: vec![] }, &()).list;
+        if list.is_empty() {
+            return false;
+        }
+        // positive indices go first, the stable sort keeps tree order for the rest
+        list.sort_by_key(|&(index, _)| (index == 0, index));
+
+        let cur = self.focused.and_then(|id| list.iter().position(|&(_, wid)| wid == id));
+        let next = match (cur, reverse) {
+            (Some(i), false) => (i + 1) % list.len(),
+            (Some(i), true) => (i + list.len() - 1) % list.len(),
+            (None, false) => 0,
+            (None, true) => list.len() - 1,
+        };
+        self.set_focus_ctx(Some(list[next].1), ctx, root)
+    }
+
+    fn set_focus_ctx<W: Widget>(&mut self, target: Option<WidgetId>, ctx: EventContext, root: &mut W) -> bool {
+        let target = target.filter(|&id| id != WidgetId::NONE);
+        if target == self.focused {
+            return false;
+        }
+        let old = std::mem::replace(&mut self.focused, target);
+        let lost_res = old.is_some_and(|id| dispatch_targeted(root, id, Event::FocusLost, ctx));
+        let gained_res = target.is_some_and(|id| dispatch_targeted(root, id, Event::FocusGained, ctx));
+        lost_res | gained_res
     }
 
     /// Update input state.
@@ -306,6 +913,11 @@ impl EventDispatcher {
         match *event {
             Event::MouseMoved(Axis::Position(pos)) => {
                 self.last_pos = pos;
+                // moving too far away cancels the click
+                let distance = self.click_settings.distance;
+                if self.press.is_some_and(|press| press.pos.distance_to(pos) > distance) {
+                    self.cancel_press();
+                }
             }
             Event::MouseButton(ButtonState::Pressed, button) => {
                 self.button_state.set(button);
@@ -321,15 +933,71 @@ impl EventDispatcher {
     }
 
     /// Creates an event context
-    fn make_context(&self) -> EventContext {
+    fn make_context(&self, timestamp: Instant) -> EventContext {
         EventContext {
-            timestamp: std::time::Instant::now(),
+            timestamp,
             local_pos: self.last_pos,
             abs_pos: self.last_pos,
             button_state: self.button_state,
             mod_state: self.mod_state,
             widget: WidgetId::NONE,
             parent: WidgetId::NONE,
+            phase: EventPhase::Target,
+        }
+    }
+}
+
+/// Target of a phased event dispatch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathTarget {
+    /// The top-most widget under a position.
+    Position(Point<f64>),
+    /// The widget with the specified id.
+    Widget(WidgetId),
+}
+
+struct PathContext {
+    abs_pos: Position,
+    abs_bounds: Option<Rect>,
+    vp_orig: Position,
+    id: WidgetId,
+    parent_id: WidgetId,
+    index: usize,
+}
+
+impl PathContext {
+    fn from_parent<W: Widget>(parent: &PathContext, widget: &W, target: PathTarget, index: usize) -> Option<Self> {
+        let abs_pos = parent.abs_pos - parent.vp_orig + widget.get_position();
+        let abs_bounds = parent
+            .abs_bounds
+            .and_then(|parent_bounds| Rect::new(abs_pos, widget.get_size()).clip_inside(parent_bounds));
+        // when looking for a position, skip the widgets that don't contain it
+        if let PathTarget::Position(pos) = target {
+            if !abs_bounds.is_some_and(|bounds| pos.inside(bounds)) {
+                return None;
+            }
+        }
+        Some(Self {
+            abs_pos,
+            abs_bounds,
+            vp_orig: widget.viewport_origin(),
+            id: widget.get_id(),
+            parent_id: parent.id,
+            index: index + 1,
+        })
+    }
+}
+
+impl From<Size> for PathContext {
+    #[inline]
+    fn from(size: Size) -> Self {
+        Self {
+            abs_pos: Default::default(),
+            abs_bounds: Some(size.into()),
+            vp_orig: Default::default(),
+            id: WidgetId::NONE,
+            parent_id: WidgetId::NONE,
+            index: 0,
         }
     }
 }
@@ -0,0 +1,303 @@
+use crate::event::{Axis, ButtonState, Event, EventContext, MouseButton, TouchId, TouchPhase};
+use crate::geometry::Point;
+use std::time::{Duration, Instant};
+
+/// Time without motion after which a pan is considered stopped.
+const VELOCITY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// High level gestures recognized from pointer and touch input.
+///
+/// Gestures are synthesized by the dispatcher and sent as `Event::Gesture` to the widget under the point
+/// where the gesture began. The widget that consumes the start of a gesture receives the rest of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A single contact (or the mouse with the left button held) is being dragged.
+    Pan {
+        phase: GesturePhase,
+        /// Movement since the last pan event, or since the contact started for the first one.
+        delta: Point<f64>,
+        /// Current speed of the movement, in pixels per second.
+        velocity: Point<f64>,
+        /// Normalized pressure of the contact, if the device reports it.
+        pressure: Option<f64>,
+        /// Tilt of the pen on both axes, if the device reports it.
+        tilt: Option<Point<f64>>,
+    },
+    /// Two contacts are moving closer or apart.
+    Pinch {
+        phase: GesturePhase,
+        /// Distance between the contacts relative to the distance when the pinch started.
+        scale: f64,
+        /// Middle point between the contacts, relative to the window.
+        center: Point<f64>,
+    },
+    /// A pan ended with enough speed. Sent after the `Pan` end.
+    Swipe(SwipeDirection),
+}
+
+impl Gesture {
+    /// Gets the phase of the gesture. Swipes are always `Ended`.
+    #[inline]
+    pub fn phase(&self) -> GesturePhase {
+        match *self {
+            Gesture::Pan { phase, .. } | Gesture::Pinch { phase, .. } => phase,
+            Gesture::Swipe(_) => GesturePhase::Ended,
+        }
+    }
+}
+
+/// Stage of a gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GesturePhase {
+    Started,
+    Moved,
+    Ended,
+}
+
+/// Direction of a swipe gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl SwipeDirection {
+    /// Gets the main direction of a movement.
+    pub fn from_vector(v: Point<f64>) -> Self {
+        if v.x.abs() >= v.y.abs() {
+            if v.x < 0.0 {
+                SwipeDirection::Left
+            } else {
+                SwipeDirection::Right
+            }
+        } else if v.y < 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        }
+    }
+}
+
+/// Settings for the gesture recognizer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureSettings {
+    /// Distance a contact must move before a pan starts.
+    pub pan_threshold: f64,
+    /// Minimum speed (in pixels per second) at the end of a pan to be considered a swipe.
+    pub swipe_velocity: f64,
+}
+
+impl Default for GestureSettings {
+    #[inline]
+    fn default() -> Self {
+        GestureSettings {
+            pan_threshold: 8.0,
+            swipe_velocity: 600.0,
+        }
+    }
+}
+
+/// Identifies the mouse pointer (`None`) or a touch contact.
+type ContactId = Option<TouchId>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// Contacts are down, but haven't moved enough yet.
+    Waiting,
+    Pan {
+        velocity: Point<f64>,
+    },
+    Pinch {
+        start_distance: f64,
+    },
+    /// The gesture ended while contacts are still down, wait until all of them are lifted.
+    Finished,
+}
+
+/// Turns pointer and touch input into gestures.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GestureRecognizer {
+    contacts: Vec<(ContactId, Point<f64>)>,
+    state: State,
+    /// Position of the first contact.
+    origin: Point<f64>,
+    last_time: Option<Instant>,
+    pressure: Option<f64>,
+    tilt: Option<Point<f64>>,
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        GestureRecognizer {
+            contacts: vec![],
+            state: State::Waiting,
+            origin: Default::default(),
+            last_time: None,
+            pressure: None,
+            tilt: None,
+        }
+    }
+}
+
+impl GestureRecognizer {
+    /// Position where the current gesture began.
+    #[inline]
+    pub fn origin(&self) -> Point<f64> {
+        self.origin
+    }
+
+    /// Feeds an input event. Returns the recognized gestures and their position.
+    pub fn update(&mut self, event: &Event, ctx: &EventContext, settings: &GestureSettings) -> Vec<(Gesture, Point<f64>)> {
+        let time = ctx.timestamp;
+        match *event {
+            Event::Touch { id, phase, pos, force } => {
+                if force.is_some() {
+                    self.pressure = force;
+                }
+                match phase {
+                    TouchPhase::Started => self.contact_start(Some(id), pos, time),
+                    TouchPhase::Moved => self.contact_moved(Some(id), pos, time, settings),
+                    TouchPhase::Ended => self.contact_end(Some(id), time, settings, true),
+                    TouchPhase::Cancelled => self.contact_end(Some(id), time, settings, false),
+                }
+            }
+            Event::MouseButton(ButtonState::Pressed, MouseButton::Left) => self.contact_start(None, ctx.abs_pos, time),
+            Event::MouseButton(ButtonState::Released, MouseButton::Left) => self.contact_end(None, time, settings, true),
+            Event::MouseMoved(Axis::Position(pos)) => self.contact_moved(None, pos, time, settings),
+            Event::MouseMoved(Axis::Pressure(pressure)) => {
+                self.pressure = Some(pressure);
+                vec![]
+            }
+            Event::MouseMoved(Axis::Tilt(x, y)) => {
+                self.tilt = Some(Point::new(x, y));
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn contact_start(&mut self, id: ContactId, pos: Point<f64>, time: Instant) -> Vec<(Gesture, Point<f64>)> {
+        if self.contacts.iter().any(|&(c, _)| c == id) {
+            return vec![];
+        }
+        self.contacts.push((id, pos));
+        if self.contacts.len() == 1 {
+            *self = GestureRecognizer {
+                contacts: std::mem::take(&mut self.contacts),
+                origin: pos,
+                last_time: Some(time),
+                pressure: self.pressure,
+                tilt: self.tilt,
+                ..Default::default()
+            };
+            return vec![];
+        }
+        if self.contacts.len() != 2 {
+            return vec![];
+        }
+
+        // a second contact turns the gesture into a pinch
+        let mut gestures = vec![];
+        match self.state {
+            State::Waiting => (),
+            State::Pan { velocity } => gestures.push((self.pan(GesturePhase::Ended, Default::default(), velocity), self.contacts[0].1)),
+            State::Pinch { .. } | State::Finished => return gestures,
+        }
+        let (a, b) = (self.contacts[0].1, self.contacts[1].1);
+        self.state = State::Pinch {
+            start_distance: a.distance_to(b).max(1.0),
+        };
+        gestures.push((self.pinch(GesturePhase::Started), a.interpolate(b, 0.5)));
+        gestures
+    }
+
+    fn contact_moved(&mut self, id: ContactId, pos: Point<f64>, time: Instant, settings: &GestureSettings) -> Vec<(Gesture, Point<f64>)> {
+        let index = match self.contacts.iter().position(|&(c, _)| c == id) {
+            Some(i) => i,
+            None => return vec![],
+        };
+        let last_pos = std::mem::replace(&mut self.contacts[index].1, pos);
+        let last_time = self.last_time.replace(time);
+
+        match self.state {
+            State::Waiting if self.contacts.len() == 1 && pos.distance_to(self.origin) >= settings.pan_threshold => {
+                self.state = State::Pan {
+                    velocity: Default::default(),
+                };
+                vec![(self.pan(GesturePhase::Started, pos - self.origin, Default::default()), pos)]
+            }
+            State::Pan { velocity } => {
+                let delta = pos - last_pos;
+                let dt = last_time.map_or(0.0, |last| time.saturating_duration_since(last).as_secs_f64());
+                let velocity = if dt > 0.0 { delta / dt } else { velocity };
+                self.state = State::Pan { velocity };
+                vec![(self.pan(GesturePhase::Moved, delta, velocity), pos)]
+            }
+            State::Pinch { .. } if index < 2 => {
+                let center = self.contacts[0].1.interpolate(self.contacts[1].1, 0.5);
+                vec![(self.pinch(GesturePhase::Moved), center)]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn contact_end(&mut self, id: ContactId, time: Instant, settings: &GestureSettings, finished: bool) -> Vec<(Gesture, Point<f64>)> {
+        let index = match self.contacts.iter().position(|&(c, _)| c == id) {
+            Some(i) => i,
+            None => return vec![],
+        };
+        let mut gestures = vec![];
+        match self.state {
+            State::Pan { velocity } => {
+                let pos = self.contacts[index].1;
+                // the contact stopped before being lifted
+                let stopped = self
+                    .last_time
+                    .is_none_or(|last| time.saturating_duration_since(last) > VELOCITY_TIMEOUT);
+                let velocity = if stopped { Default::default() } else { velocity };
+                gestures.push((self.pan(GesturePhase::Ended, Default::default(), velocity), pos));
+                if finished && velocity.distance_to(Default::default()) >= settings.swipe_velocity {
+                    gestures.push((Gesture::Swipe(SwipeDirection::from_vector(velocity)), pos));
+                }
+                self.state = State::Finished;
+            }
+            State::Pinch { .. } if index < 2 => {
+                let center = self.contacts[0].1.interpolate(self.contacts[1].1, 0.5);
+                gestures.push((self.pinch(GesturePhase::Ended), center));
+                self.state = State::Finished;
+            }
+            _ => (),
+        }
+        self.contacts.remove(index);
+        if self.contacts.is_empty() {
+            self.state = State::Waiting;
+        }
+        gestures
+    }
+
+    #[inline]
+    fn pan(&self, phase: GesturePhase, delta: Point<f64>, velocity: Point<f64>) -> Gesture {
+        Gesture::Pan {
+            phase,
+            delta,
+            velocity,
+            pressure: self.pressure,
+            tilt: self.tilt,
+        }
+    }
+
+    fn pinch(&self, phase: GesturePhase) -> Gesture {
+        let (a, b) = (self.contacts[0].1, self.contacts[1].1);
+        let start_distance = match self.state {
+            State::Pinch { start_distance } => start_distance,
+            _ => 1.0,
+        };
+        Gesture::Pinch {
+            phase,
+            scale: a.distance_to(b) / start_distance,
+            center: a.interpolate(b, 0.5),
+        }
+    }
+}
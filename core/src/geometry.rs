@@ -3,6 +3,8 @@ mod point;
 pub use point::Point;
 mod size;
 pub use size::Size;
+mod size_hint;
+pub use size_hint::SizeHint;
 mod rect;
 pub use rect::Rect;
 mod border;
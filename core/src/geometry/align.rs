@@ -39,6 +39,34 @@ impl Default for Alignment {
 
 impl_from_unit_default!(Alignment);
 
+/// Position of an alignment inside the available space.
+pub trait AlignValue {
+    /// Gets the aligned position as a fraction of the free space: 0 for the start, 0.5 for the center and 1 for the end.
+    fn value(self) -> f32;
+}
+
+impl AlignValue for HAlign {
+    #[inline]
+    fn value(self) -> f32 {
+        match self {
+            HAlign::Left => 0.0,
+            HAlign::Center => 0.5,
+            HAlign::Right => 1.0,
+        }
+    }
+}
+
+impl AlignValue for VAlign {
+    #[inline]
+    fn value(self) -> f32 {
+        match self {
+            VAlign::Top => 0.0,
+            VAlign::Center => 0.5,
+            VAlign::Bottom => 1.0,
+        }
+    }
+}
+
 /* TODO: pull request this stuff, or make a wrapper
 /// Horizontal alignment.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -59,6 +59,23 @@ impl Rect {
         }
     }
 
+    /// Creates the smallest rectangle that covers the area between two floating point corners.
+    #[inline]
+    pub fn enclosing(min: Point<f32>, max: Point<f32>) -> Self {
+        let x0 = min.x.floor();
+        let y0 = min.y.floor();
+        Rect {
+            pos: Point {
+                x: x0 as i32,
+                y: y0 as i32,
+            },
+            size: Size {
+                w: (max.x.ceil() - x0).max(0.0) as u32,
+                h: (max.y.ceil() - y0).max(0.0) as u32,
+            },
+        }
+    }
+
     #[inline]
     pub fn x(self) -> i32 {
         self.pos.x
@@ -27,6 +27,12 @@ impl Size {
         Size { w: 1, h: 1 }
     }
 
+    /// Largest possible size, used to represent unlimited space.
+    #[inline]
+    pub const fn unbounded() -> Self {
+        Size { w: u32::MAX, h: u32::MAX }
+    }
+
     #[inline]
     pub const fn square(side: u32) -> Self {
         Size { w: side, h: side }
@@ -121,6 +127,11 @@ impl Size {
         self.map2(rhs, u32::saturating_sub)
     }
 
+    #[inline]
+    pub fn saturating_add(self, rhs: Size) -> Self {
+        self.map2(rhs, u32::saturating_add)
+    }
+
     implement_map!(u32, w, h);
 }
 
@@ -0,0 +1,75 @@
+use crate::geometry::{Border, Size};
+
+/// Size requirements of a widget, reported by `Widget::measure`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SizeHint {
+    /// Smallest size the widget can be used at.
+    pub min: Size,
+    /// Size the widget needs to show all it's contents.
+    pub preferred: Size,
+    /// Largest size the widget can make use of.
+    pub max: Size,
+}
+
+impl SizeHint {
+    #[inline]
+    pub fn new(min: impl Into<Size>, preferred: impl Into<Size>, max: impl Into<Size>) -> Self {
+        SizeHint {
+            min: min.into(),
+            preferred: preferred.into(),
+            max: max.into(),
+        }
+    }
+
+    /// Creates a hint for a widget that can only have a single size.
+    #[inline]
+    pub fn fixed(size: impl Into<Size>) -> Self {
+        let size = size.into();
+        SizeHint {
+            min: size,
+            preferred: size,
+            max: size,
+        }
+    }
+
+    /// Creates a hint for a widget that can take any size, starting from the preferred one.
+    #[inline]
+    pub fn flexible(preferred: impl Into<Size>) -> Self {
+        SizeHint {
+            min: Size::zero(),
+            preferred: preferred.into(),
+            max: Size::unbounded(),
+        }
+    }
+
+    /// Limits a size to the `min`..`max` range.
+    #[inline]
+    pub fn clamp(&self, size: Size) -> Size {
+        size.map2(self.max, u32::min).map2(self.min, u32::max)
+    }
+
+    /// Adds a border to all the sizes.
+    #[inline]
+    pub fn add_border(self, border: Border) -> Self {
+        let border = Size::zero().add_border(border);
+        SizeHint {
+            min: self.min.saturating_add(border),
+            preferred: self.preferred.saturating_add(border),
+            max: self.max.saturating_add(border),
+        }
+    }
+}
+
+impl Default for SizeHint {
+    #[inline]
+    fn default() -> Self {
+        SizeHint::flexible(Size::zero())
+    }
+}
+
+impl From<Size> for SizeHint {
+    #[inline]
+    fn from(size: Size) -> Self {
+        SizeHint::fixed(size)
+    }
+}
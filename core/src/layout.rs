@@ -1,5 +1,14 @@
 //! Helper methods for composing widget layouts.
-use crate::geometry::{Bounds, BoundsMut, VAlign};
+mod boxes;
+#[cfg(feature = "constraint")]
+pub mod constraint;
+pub use boxes::*;
+mod grid;
+pub use grid::*;
+mod flex;
+pub use flex::*;
+
+use crate::geometry::{AlignValue, Bounds, BoundsMut, VAlign};
 
 /// Bounds extension for placing widgets relative to others.
 pub trait Layout: BoundsMut {
@@ -125,11 +134,7 @@ where
     T: BoundsMut + 'a,
     I: IntoIterator<Item = &'a mut T>,
 {
-    let align_val = match valign {
-        VAlign::Top => 0.0,
-        VAlign::Center => 0.5,
-        VAlign::Bottom => 1.0,
-    };
+    let align_val = valign.value();
     let mut row_items = vec![];
     let mut iter = items.into_iter();
 
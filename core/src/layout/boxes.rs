@@ -0,0 +1,228 @@
+use crate::geometry::{AlignValue, Alignment, BoundsMut, Position, Rect, Size};
+
+/// Size constraints of an item along the direction of a box layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoxItem {
+    /// Initial length of the item.
+    pub size: u32,
+    /// Share of the free space this item takes, relative to the other items. Zero means it doesn't grow.
+    pub stretch: u32,
+    /// Smallest length the item can shrink to.
+    pub min: u32,
+    /// Largest length the item can grow to.
+    pub max: u32,
+    /// Use the full size of the other axis instead of aligning the item.
+    pub fill: bool,
+}
+
+impl BoxItem {
+    /// Creates an item that always has the same length.
+    #[inline]
+    pub const fn fixed(size: u32) -> Self {
+        BoxItem {
+            size,
+            stretch: 0,
+            min: size,
+            max: size,
+            fill: false,
+        }
+    }
+
+    /// Creates an item that starts empty and takes a share of the free space.
+    #[inline]
+    pub const fn stretch(factor: u32) -> Self {
+        BoxItem {
+            size: 0,
+            stretch: factor,
+            min: 0,
+            max: u32::MAX,
+            fill: false,
+        }
+    }
+
+    /// Sets the initial length.
+    #[inline]
+    pub fn with_size(self, size: u32) -> Self {
+        BoxItem { size, ..self }
+    }
+
+    /// Sets the minimum length.
+    #[inline]
+    pub fn with_min(self, min: u32) -> Self {
+        BoxItem { min, ..self }
+    }
+
+    /// Sets the maximum length.
+    #[inline]
+    pub fn with_max(self, max: u32) -> Self {
+        BoxItem { max, ..self }
+    }
+
+    /// Makes the item use the full size of the other axis.
+    #[inline]
+    pub fn with_fill(self) -> Self {
+        BoxItem { fill: true, ..self }
+    }
+
+    /// The initial length limited to the `min`..`max` range.
+    #[inline]
+    fn clamped_size(&self) -> u32 {
+        self.size.min(self.max).max(self.min)
+    }
+}
+
+impl Default for BoxItem {
+    #[inline]
+    fn default() -> Self {
+        BoxItem::stretch(1)
+    }
+}
+
+/// Places a collection of widgets in a row inside `rect`, distributing the width between them.
+///
+/// Items start at their `BoxItem` size. The free space is shared by the stretchable items in proportion to their stretch
+/// factors. If the items don't fit, they shrink towards their minimum size. The horizontal alignment positions the row
+/// when it doesn't fill the width, and the vertical alignment positions each item inside the row.
+pub fn hbox<'a, T, I>(items: I, rect: Rect, spacing: u32, align: impl Into<Alignment>)
+where
+    T: BoundsMut + 'a,
+    I: IntoIterator<Item = (&'a mut T, BoxItem)>,
+{
+    linear_box(items, rect, spacing, align.into(), BoxAxis::Horizontal)
+}
+
+/// Places a collection of widgets in a column inside `rect`, distributing the height between them.
+///
+/// This is the vertical version of `hbox`.
+pub fn vbox<'a, T, I>(items: I, rect: Rect, spacing: u32, align: impl Into<Alignment>)
+where
+    T: BoundsMut + 'a,
+    I: IntoIterator<Item = (&'a mut T, BoxItem)>,
+{
+    linear_box(items, rect, spacing, align.into(), BoxAxis::Vertical)
+}
+
+/// Direction the items of a box layout are placed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BoxAxis {
+    Horizontal,
+    Vertical,
+}
+
+impl BoxAxis {
+    /// Splits a size into it's main and cross axis components.
+    #[inline]
+    fn axes(self, size: Size) -> (u32, u32) {
+        match self {
+            BoxAxis::Horizontal => (size.w, size.h),
+            BoxAxis::Vertical => (size.h, size.w),
+        }
+    }
+
+    /// Builds a rectangle from main and cross axis components.
+    #[inline]
+    fn rect(self, origin: Position, main_pos: i32, cross_pos: i32, main: u32, cross: u32) -> Rect {
+        match self {
+            BoxAxis::Horizontal => Rect::new(origin.offset(main_pos, cross_pos), Size::new(main, cross)),
+            BoxAxis::Vertical => Rect::new(origin.offset(cross_pos, main_pos), Size::new(cross, main)),
+        }
+    }
+}
+
+/// Implements `hbox` and `vbox`, with the items placed along `direction`.
+fn linear_box<'a, T, I>(items: I, rect: Rect, spacing: u32, align: Alignment, direction: BoxAxis)
+where
+    T: BoundsMut + 'a,
+    I: IntoIterator<Item = (&'a mut T, BoxItem)>,
+{
+    let (main_len, cross_len) = direction.axes(rect.size);
+    let (main_align, cross_align) = match direction {
+        BoxAxis::Horizontal => (align.horizontal.value(), align.vertical.value()),
+        BoxAxis::Vertical => (align.vertical.value(), align.horizontal.value()),
+    };
+    let (items, specs): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+    let lengths = distribute(&specs, main_len, spacing);
+    let used = lengths.iter().map(|&l| l as u64).sum::<u64>() + spacing as u64 * lengths.len().saturating_sub(1) as u64;
+    let mut main_pos = (main_len.saturating_sub(used as u32) as f32 * main_align) as i32;
+
+    for ((item, spec), main) in items.into_iter().zip(specs).zip(lengths) {
+        let cross = if spec.fill {
+            cross_len
+        } else {
+            direction.axes(item.get_size()).1.min(cross_len)
+        };
+        let cross_pos = ((cross_len - cross) as f32 * cross_align) as i32;
+        item.set_bounds(direction.rect(rect.pos, main_pos, cross_pos, main, cross));
+        main_pos += (main + spacing) as i32;
+    }
+}
+
+/// Computes the length of each item so they fill `length` as best as possible.
+fn distribute(specs: &[BoxItem], length: u32, spacing: u32) -> Vec<u32> {
+    let mut sizes: Vec<u32> = specs.iter().map(BoxItem::clamped_size).collect();
+    let avail = (length as u64).saturating_sub(spacing as u64 * specs.len().saturating_sub(1) as u64);
+    let total: u64 = sizes.iter().map(|&s| s as u64).sum();
+
+    if total < avail {
+        // grow the stretchable items, the ones that reach their max leave the rest to the others
+        let mut extra = avail - total;
+        let mut growing: Vec<usize> = (0..specs.len())
+            .filter(|&i| specs[i].stretch > 0 && sizes[i] < specs[i].max)
+            .collect();
+        while extra > 0 && !growing.is_empty() {
+            let total_stretch: u64 = growing.iter().map(|&i| specs[i].stretch as u64).sum();
+            let share = |i: usize| extra * specs[i].stretch as u64 / total_stretch;
+            let capped: Vec<usize> = growing
+                .iter()
+                .copied()
+                .filter(|&i| sizes[i] as u64 + share(i) >= specs[i].max as u64)
+                .collect();
+            if capped.is_empty() {
+                let mut given = 0;
+                for &i in &growing {
+                    sizes[i] += share(i) as u32;
+                    given += share(i);
+                }
+                // the rounding leftover goes to the first items
+                for &i in growing.iter().take((extra - given) as usize) {
+                    sizes[i] += 1;
+                }
+                break;
+            }
+            for i in capped {
+                extra -= (specs[i].max - sizes[i]) as u64;
+                sizes[i] = specs[i].max;
+                growing.retain(|&j| j != i);
+            }
+        }
+    } else if total > avail {
+        // shrink all items towards their min, in proportion to how much they can shrink
+        let deficit = total - avail;
+        let slack: Vec<u64> = (0..specs.len()).map(|i| (sizes[i] - specs[i].min) as u64).collect();
+        let total_slack: u64 = slack.iter().sum();
+        if deficit >= total_slack {
+            for (size, spec) in sizes.iter_mut().zip(specs) {
+                *size = spec.min;
+            }
+        } else {
+            let mut taken = 0;
+            for i in 0..specs.len() {
+                let cut = deficit * slack[i] / total_slack;
+                sizes[i] -= cut as u32;
+                taken += cut;
+            }
+            let mut left = deficit - taken;
+            for i in 0..specs.len() {
+                if left == 0 {
+                    break;
+                }
+                if sizes[i] > specs[i].min {
+                    sizes[i] -= 1;
+                    left -= 1;
+                }
+            }
+        }
+    }
+
+    sizes
+}
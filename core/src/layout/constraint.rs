@@ -0,0 +1,247 @@
+//! Layout defined by linear constraints between widget edges.
+//!
+//! Constraints are written with the operators from the `cassowary` crate, for example
+//! `(a.right() + 8.0) | EQ(REQUIRED) | b.left` or `b.width | GE(STRONG) | 100.0`. Non required constraints are
+//! satisfied as best as possible according to their strength.
+use crate::geometry::{BoundsMut, Position, Rect, Size};
+use cassowary::{AddConstraintError, RemoveConstraintError, Solver};
+use std::fmt;
+
+pub use cassowary::strength::{self, MEDIUM, REQUIRED, STRONG, WEAK};
+pub use cassowary::WeightedRelation::{self, EQ, GE, LE};
+pub use cassowary::{Constraint, Expression, Variable};
+
+/// Solver variables for the bounds of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Anchors {
+    pub left: Variable,
+    pub top: Variable,
+    pub width: Variable,
+    pub height: Variable,
+}
+
+impl Anchors {
+    fn new() -> Self {
+        Anchors {
+            left: Variable::new(),
+            top: Variable::new(),
+            width: Variable::new(),
+            height: Variable::new(),
+        }
+    }
+
+    /// Position of the right edge.
+    #[inline]
+    pub fn right(&self) -> Expression {
+        self.left + self.width
+    }
+
+    /// Position of the bottom edge.
+    #[inline]
+    pub fn bottom(&self) -> Expression {
+        self.top + self.height
+    }
+
+    /// Position of the horizontal center.
+    #[inline]
+    pub fn center_x(&self) -> Expression {
+        self.left + self.width / 2.0
+    }
+
+    /// Position of the vertical center.
+    #[inline]
+    pub fn center_y(&self) -> Expression {
+        self.top + self.height / 2.0
+    }
+}
+
+/// Error produced when changing the constraints of a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConstraintError {
+    /// The constraint was already added.
+    Duplicate,
+    /// The constraint is required but conflicts with the other required constraints.
+    Unsatisfiable,
+    /// The constraint wasn't added to the layout.
+    Unknown,
+    /// The solver reached an invalid state.
+    Internal(&'static str),
+}
+
+impl From<AddConstraintError> for ConstraintError {
+    #[inline]
+    fn from(err: AddConstraintError) -> Self {
+        match err {
+            AddConstraintError::DuplicateConstraint => ConstraintError::Duplicate,
+            AddConstraintError::UnsatisfiableConstraint => ConstraintError::Unsatisfiable,
+            AddConstraintError::InternalSolverError(msg) => ConstraintError::Internal(msg),
+        }
+    }
+}
+
+impl From<RemoveConstraintError> for ConstraintError {
+    #[inline]
+    fn from(err: RemoveConstraintError) -> Self {
+        match err {
+            RemoveConstraintError::UnknownConstraint => ConstraintError::Unknown,
+            RemoveConstraintError::InternalSolverError(msg) => ConstraintError::Internal(msg),
+        }
+    }
+}
+
+impl fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Duplicate => write!(f, "The constraint was already added"),
+            Self::Unsatisfiable => write!(f, "The required constraint can't be satisfied"),
+            Self::Unknown => write!(f, "The constraint is not part of the layout"),
+            Self::Internal(msg) => write!(f, "Internal constraint solver error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ConstraintError {}
+
+/// Container layout solved from constraints between it's items.
+///
+/// The container bounds are available with `parent`, and are set to the layout rectangle when applying it.
+/// A constraint that fails to be added leaves the layout unchanged.
+pub struct ConstraintLayout {
+    solver: Solver,
+    parent: Anchors,
+    /// The constraints added to the solver, used to rebuild it after a failure.
+    constraints: Vec<Constraint>,
+    /// The last applied container bounds.
+    rect: Rect,
+}
+
+impl ConstraintLayout {
+    /// Creates an empty layout.
+    pub fn new() -> Self {
+        let parent = Anchors::new();
+        ConstraintLayout {
+            solver: new_solver(&parent),
+            parent,
+            constraints: vec![],
+            rect: Default::default(),
+        }
+    }
+
+    /// Gets the variables of the container bounds.
+    #[inline]
+    pub fn parent(&self) -> Anchors {
+        self.parent
+    }
+
+    /// Creates the variables for a new item. Items can't have a negative size.
+    pub fn add_item(&mut self) -> Anchors {
+        let item = Anchors::new();
+        self.add_constraints(vec![item.width | GE(REQUIRED) | 0.0, item.height | GE(REQUIRED) | 0.0])
+            .expect("new variables have no constraints");
+        item
+    }
+
+    /// Adds a constraint.
+    pub fn add_constraint(&mut self, constraint: Constraint) -> Result<(), ConstraintError> {
+        match self.solver.add_constraint(constraint.clone()) {
+            Ok(()) => {
+                self.constraints.push(constraint);
+                Ok(())
+            }
+            Err(AddConstraintError::DuplicateConstraint) => Err(ConstraintError::Duplicate),
+            Err(err) => {
+                // the solver can be left in an inconsistent state
+                self.rebuild();
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Adds a collection of constraints, stopping at the first one that fails.
+    pub fn add_constraints(&mut self, constraints: impl IntoIterator<Item = Constraint>) -> Result<(), ConstraintError> {
+        constraints.into_iter().try_for_each(|c| self.add_constraint(c))
+    }
+
+    /// Removes a previously added constraint.
+    pub fn remove_constraint(&mut self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        self.solver.remove_constraint(constraint)?;
+        self.constraints.retain(|c| c != constraint);
+        Ok(())
+    }
+
+    /// Checks if the constraint was added.
+    #[inline]
+    pub fn has_constraint(&self, constraint: &Constraint) -> bool {
+        self.solver.has_constraint(constraint)
+    }
+
+    /// Solves the layout for the container bounds in `rect`, and sets the bounds of each item.
+    pub fn apply<'a, T, I>(&mut self, items: I, rect: Rect)
+    where
+        T: BoundsMut + 'a,
+        I: IntoIterator<Item = (&'a mut T, Anchors)>,
+    {
+        self.rect = rect;
+        self.suggest_parent();
+        for (item, anchors) in items {
+            item.set_bounds(self.bounds(&anchors));
+        }
+    }
+
+    /// Gets the bounds of an item from the last solution, rounded to the pixel grid.
+    pub fn bounds(&self, anchors: &Anchors) -> Rect {
+        let left = self.solver.get_value(anchors.left);
+        let top = self.solver.get_value(anchors.top);
+        let x0 = left.round();
+        let y0 = top.round();
+        let x1 = (left + self.solver.get_value(anchors.width)).round().max(x0);
+        let y1 = (top + self.solver.get_value(anchors.height)).round().max(y0);
+        Rect::new(Position::new(x0 as i32, y0 as i32), Size::new((x1 - x0) as u32, (y1 - y0) as u32))
+    }
+
+    fn suggest_parent(&mut self) {
+        let (parent, rect) = (self.parent, self.rect);
+        let values = [
+            (parent.left, rect.pos.x as f64),
+            (parent.top, rect.pos.y as f64),
+            (parent.width, rect.size.w as f64),
+            (parent.height, rect.size.h as f64),
+        ];
+        for &(var, value) in &values {
+            self.solver.suggest_value(var, value).expect("parent variables are editable");
+        }
+    }
+
+    /// Creates a new solver with the current constraints.
+    fn rebuild(&mut self) {
+        self.solver = new_solver(&self.parent);
+        self.solver
+            .add_constraints(&self.constraints)
+            .expect("the previous constraints are satisfiable");
+        self.suggest_parent();
+    }
+}
+
+/// Creates a solver where the container bounds win over any constraint that isn't required.
+fn new_solver(parent: &Anchors) -> Solver {
+    let mut solver = Solver::new();
+    for &var in &[parent.left, parent.top, parent.width, parent.height] {
+        solver.add_edit_variable(var, REQUIRED - 1.0).expect("new variables can be edited");
+    }
+    solver
+}
+
+impl Default for ConstraintLayout {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ConstraintLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConstraintLayout")
+            .field("parent", &self.parent)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,282 @@
+use crate::geometry::{BoundsMut, Position, Rect, Size};
+
+/// Main axis of a flex layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlexDirection {
+    /// Items are placed from left to right.
+    Row,
+    /// Items are placed from top to bottom.
+    Column,
+}
+
+/// Distribution of the free space between the items of a flex line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Justify {
+    /// Items are packed at the start of the line.
+    Start,
+    /// Items are packed at the end of the line.
+    End,
+    /// Items are packed at the center of the line.
+    Center,
+    /// The space goes between the items, with no space at the ends.
+    SpaceBetween,
+    /// Every item gets the same space at both sides.
+    SpaceAround,
+    /// The space between items and at the ends is the same.
+    SpaceEvenly,
+}
+
+/// Placement of the items on the cross axis of a flex line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlignItems {
+    Start,
+    End,
+    Center,
+    /// Items take the full size of the line.
+    Stretch,
+}
+
+/// Flex properties of an item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexItem {
+    /// Share of the free space this item grows into, relative to the other items.
+    pub grow: f32,
+    /// How much this item shrinks when the line overflows, relative to the other items and scaled by it's basis.
+    pub shrink: f32,
+    /// Initial size on the main axis. If unset, the current item size is used.
+    pub basis: Option<u32>,
+}
+
+impl FlexItem {
+    #[inline]
+    pub fn new(grow: f32, shrink: f32, basis: Option<u32>) -> Self {
+        FlexItem { grow, shrink, basis }
+    }
+
+    /// Sets the grow factor.
+    #[inline]
+    pub fn with_grow(self, grow: f32) -> Self {
+        FlexItem { grow, ..self }
+    }
+
+    /// Sets the shrink factor.
+    #[inline]
+    pub fn with_shrink(self, shrink: f32) -> Self {
+        FlexItem { shrink, ..self }
+    }
+
+    /// Sets the initial size.
+    #[inline]
+    pub fn with_basis(self, basis: u32) -> Self {
+        FlexItem {
+            basis: Some(basis),
+            ..self
+        }
+    }
+}
+
+impl Default for FlexItem {
+    #[inline]
+    fn default() -> Self {
+        FlexItem {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+        }
+    }
+}
+
+/// Settings of a flex layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlexLayout {
+    pub direction: FlexDirection,
+    /// Start a new line when the items don't fit on the main axis.
+    pub wrap: bool,
+    pub justify: Justify,
+    pub align_items: AlignItems,
+    /// Space between items (width) and lines (height) of a row layout, swapped for a column layout.
+    pub gap: Size,
+}
+
+impl FlexLayout {
+    #[inline]
+    pub fn new(direction: FlexDirection) -> Self {
+        FlexLayout {
+            direction,
+            ..Default::default()
+        }
+    }
+
+    /// Sets if the items wrap into multiple lines.
+    #[inline]
+    pub fn with_wrap(self, wrap: bool) -> Self {
+        FlexLayout { wrap, ..self }
+    }
+
+    /// Sets the distribution of the free space on the main axis.
+    #[inline]
+    pub fn with_justify(self, justify: Justify) -> Self {
+        FlexLayout { justify, ..self }
+    }
+
+    /// Sets the placement of the items on the cross axis.
+    #[inline]
+    pub fn with_align_items(self, align_items: AlignItems) -> Self {
+        FlexLayout { align_items, ..self }
+    }
+
+    /// Sets the space between items and lines.
+    #[inline]
+    pub fn with_gap(self, gap: impl Into<Size>) -> Self {
+        FlexLayout { gap: gap.into(), ..self }
+    }
+
+    /// Splits a size into it's main and cross axis components.
+    #[inline]
+    fn axes(&self, size: Size) -> (u32, u32) {
+        match self.direction {
+            FlexDirection::Row => (size.w, size.h),
+            FlexDirection::Column => (size.h, size.w),
+        }
+    }
+
+    /// Builds a rectangle from main and cross axis components.
+    #[inline]
+    fn rect(&self, origin: Position, main_pos: i32, cross_pos: i32, main: u32, cross: u32) -> Rect {
+        match self.direction {
+            FlexDirection::Row => Rect::new(origin.offset(main_pos, cross_pos), Size::new(main, cross)),
+            FlexDirection::Column => Rect::new(origin.offset(cross_pos, main_pos), Size::new(cross, main)),
+        }
+    }
+}
+
+impl Default for FlexLayout {
+    #[inline]
+    fn default() -> Self {
+        FlexLayout {
+            direction: FlexDirection::Row,
+            wrap: false,
+            justify: Justify::Start,
+            align_items: AlignItems::Stretch,
+            gap: Size::zero(),
+        }
+    }
+}
+
+/// Places a collection of widgets inside `rect` following the flexbox model.
+///
+/// Items are placed in lines along the main axis, starting at their basis size. The free space of each line is
+/// given to the items that grow, or taken from the ones that shrink when the line overflows, then `justify` distributes
+/// what's left. Without wrapping the single line fills the cross axis, otherwise each line is as large as it's
+/// largest item.
+pub fn flex<'a, T, I>(items: I, rect: Rect, layout: &FlexLayout)
+where
+    T: BoundsMut + 'a,
+    I: IntoIterator<Item = (&'a mut T, FlexItem)>,
+{
+    let (main_len, cross_len) = layout.axes(rect.size);
+    let (main_gap, cross_gap) = layout.axes(layout.gap);
+
+    // (item, flex, basis, cross size)
+    let mut items: Vec<_> = items
+        .into_iter()
+        .map(|(item, flex)| {
+            let (main, cross) = layout.axes(item.get_size());
+            let basis = flex.basis.unwrap_or(main);
+            (item, flex, basis, cross)
+        })
+        .collect();
+
+    // break the items into lines
+    let mut lines = vec![];
+    let mut start = 0;
+    let mut line_len = 0;
+    for (i, &(_, _, basis, _)) in items.iter().enumerate() {
+        if layout.wrap && i > start && line_len + main_gap as u64 + basis as u64 > main_len as u64 {
+            lines.push(start..i);
+            start = i;
+        }
+        line_len = if i == start {
+            basis as u64
+        } else {
+            line_len + main_gap as u64 + basis as u64
+        };
+    }
+    if start < items.len() {
+        lines.push(start..items.len());
+    }
+
+    let mut cross_pos = 0;
+    for range in lines {
+        let line = &mut items[range];
+        let line_cross = if layout.wrap {
+            line.iter().map(|&(_, _, _, cross)| cross).max().unwrap_or(0)
+        } else {
+            cross_len
+        };
+
+        // resolve the main axis sizes
+        let n = line.len() as f64;
+        let gaps = main_gap as f64 * (n - 1.0);
+        let free = main_len as f64 - gaps - line.iter().map(|&(_, _, basis, _)| basis as f64).sum::<f64>();
+        let total_grow: f64 = line.iter().map(|(_, flex, ..)| flex.grow.max(0.0) as f64).sum();
+        let total_shrink: f64 = line
+            .iter()
+            .map(|&(_, flex, basis, _)| flex.shrink.max(0.0) as f64 * basis as f64)
+            .sum();
+        let sizes: Vec<f64> = line
+            .iter()
+            .map(|&(_, flex, basis, _)| {
+                let basis = basis as f64;
+                if free > 0.0 && total_grow > 0.0 {
+                    basis + free * flex.grow.max(0.0) as f64 / total_grow
+                } else if free < 0.0 && total_shrink > 0.0 {
+                    (basis + free * flex.shrink.max(0.0) as f64 * basis / total_shrink).max(0.0)
+                } else {
+                    basis
+                }
+            })
+            .collect();
+
+        // distribute the remaining space
+        let left = (main_len as f64 - gaps - sizes.iter().sum::<f64>()).max(0.0);
+        let (lead, between) = match layout.justify {
+            Justify::Start => (0.0, 0.0),
+            Justify::End => (left, 0.0),
+            Justify::Center => (left / 2.0, 0.0),
+            Justify::SpaceBetween if n > 1.0 => (0.0, left / (n - 1.0)),
+            Justify::SpaceBetween => (0.0, 0.0),
+            Justify::SpaceAround => (left / n / 2.0, left / n),
+            Justify::SpaceEvenly => (left / (n + 1.0), left / (n + 1.0)),
+        };
+
+        // positions are rounded from the accumulated offset so the items don't drift apart
+        let mut offset = lead;
+        for ((item, _, _, cross), size) in line.iter_mut().zip(sizes) {
+            let main_start = offset.round() as i32;
+            let main_end = (offset + size).round() as i32;
+            offset += size + main_gap as f64 + between;
+
+            let cross = if layout.align_items == AlignItems::Stretch {
+                line_cross
+            } else {
+                (*cross).min(line_cross)
+            };
+            let cross_offset = match layout.align_items {
+                AlignItems::Start | AlignItems::Stretch => 0,
+                AlignItems::End => line_cross - cross,
+                AlignItems::Center => (line_cross - cross) / 2,
+            };
+            let bounds = layout.rect(
+                rect.pos,
+                main_start,
+                cross_pos + cross_offset as i32,
+                (main_end - main_start) as u32,
+                cross,
+            );
+            item.set_bounds(bounds);
+        }
+
+        cross_pos += (line_cross + cross_gap) as i32;
+    }
+}
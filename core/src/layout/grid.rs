@@ -0,0 +1,217 @@
+use crate::geometry::{AlignValue, BoundsMut, HAlign, Position, Rect, Size, VAlign};
+
+/// Size of a grid row or column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Track {
+    /// Sized to the largest item in it.
+    Auto,
+    /// Always has the same size.
+    Fixed(u32),
+    /// Takes a share of the space left by the other tracks, relative to the other fractions.
+    Fraction(u32),
+}
+
+impl Default for Track {
+    #[inline]
+    fn default() -> Self {
+        Track::Auto
+    }
+}
+
+/// Placement of an item in a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GridCell {
+    /// First column of the cell.
+    pub col: usize,
+    /// First row of the cell.
+    pub row: usize,
+    /// Number of columns the cell spans.
+    pub col_span: usize,
+    /// Number of rows the cell spans.
+    pub row_span: usize,
+    /// Horizontal alignment of the item inside the cell.
+    pub halign: HAlign,
+    /// Vertical alignment of the item inside the cell.
+    pub valign: VAlign,
+    /// Resize the item to the full cell size instead of aligning it.
+    pub fill: bool,
+}
+
+impl GridCell {
+    /// Creates a single cell at the specified column and row.
+    #[inline]
+    pub const fn new(col: usize, row: usize) -> Self {
+        GridCell {
+            col,
+            row,
+            col_span: 1,
+            row_span: 1,
+            halign: HAlign::Left,
+            valign: VAlign::Top,
+            fill: false,
+        }
+    }
+
+    /// Sets the number of columns and rows the cell spans.
+    #[inline]
+    pub fn with_span(self, col_span: usize, row_span: usize) -> Self {
+        GridCell {
+            col_span,
+            row_span,
+            ..self
+        }
+    }
+
+    /// Sets the alignment of the item inside the cell.
+    #[inline]
+    pub fn with_align(self, halign: HAlign, valign: VAlign) -> Self {
+        GridCell { halign, valign, ..self }
+    }
+
+    /// Makes the item use the full cell size.
+    #[inline]
+    pub fn with_fill(self) -> Self {
+        GridCell { fill: true, ..self }
+    }
+}
+
+/// Places a collection of widgets in the cells of a grid inside `rect`.
+///
+/// `Auto` tracks take the size of their largest item, and items spanning several tracks enlarge the `Auto` tracks
+/// they cover when they don't fit, unless they also cover a `Fraction` track. `Fraction` tracks share the space
+/// left after that. The `gap` is the space between columns (width) and rows (height). Items with cells outside the
+/// grid are left unchanged.
+pub fn grid<'a, T, I>(items: I, rect: Rect, columns: &[Track], rows: &[Track], gap: Size)
+where
+    T: BoundsMut + 'a,
+    I: IntoIterator<Item = (&'a mut T, GridCell)>,
+{
+    let items: Vec<_> = items
+        .into_iter()
+        .filter(|(_, cell)| cell.col < columns.len() && cell.row < rows.len())
+        .map(|(item, cell)| {
+            // cut the spans to the grid size
+            let col_span = cell.col_span.max(1).min(columns.len() - cell.col);
+            let row_span = cell.row_span.max(1).min(rows.len() - cell.row);
+            (
+                item,
+                GridCell {
+                    col_span,
+                    row_span,
+                    ..cell
+                },
+            )
+        })
+        .collect();
+
+    let widths: Vec<_> = items
+        .iter()
+        .map(|(item, cell)| (cell.col, cell.col_span, item.get_size().w))
+        .collect();
+    let heights: Vec<_> = items
+        .iter()
+        .map(|(item, cell)| (cell.row, cell.row_span, item.get_size().h))
+        .collect();
+    let col_pos = track_offsets(&track_sizes(columns, &widths, rect.size.w, gap.w), rect.pos.x, gap.w);
+    let row_pos = track_offsets(&track_sizes(rows, &heights, rect.size.h, gap.h), rect.pos.y, gap.h);
+
+    for (item, cell) in items {
+        let x0 = col_pos[cell.col].0;
+        let x1 = col_pos[cell.col + cell.col_span - 1].1;
+        let y0 = row_pos[cell.row].0;
+        let y1 = row_pos[cell.row + cell.row_span - 1].1;
+        let cell_size = Size::new((x1 - x0) as u32, (y1 - y0) as u32);
+        let size = if cell.fill {
+            cell_size
+        } else {
+            item.get_size().map2(cell_size, u32::min)
+        };
+        let dx = ((cell_size.w - size.w) as f32 * cell.halign.value()) as i32;
+        let dy = ((cell_size.h - size.h) as f32 * cell.valign.value()) as i32;
+        item.set_bounds(Rect::new(Position::new(x0 + dx, y0 + dy), size));
+    }
+}
+
+/// Computes the size of each track from the (start, span, size) of the items in them.
+fn track_sizes(tracks: &[Track], items: &[(usize, usize, u32)], length: u32, gap: u32) -> Vec<u32> {
+    let mut sizes: Vec<u32> = tracks
+        .iter()
+        .map(|track| match *track {
+            Track::Fixed(size) => size,
+            _ => 0,
+        })
+        .collect();
+
+    // auto tracks fit the items that are only in them
+    for &(start, _, size) in items.iter().filter(|&&(_, span, _)| span == 1) {
+        if tracks[start] == Track::Auto {
+            sizes[start] = sizes[start].max(size);
+        }
+    }
+
+    // spanning items that don't fit enlarge the auto tracks they cover
+    for &(start, span, size) in items.iter().filter(|&&(_, span, _)| span > 1) {
+        let range = start..start + span;
+        let current = sizes[range.clone()].iter().sum::<u32>() + gap * (span as u32 - 1);
+        if size <= current || tracks[range.clone()].iter().any(|track| matches!(track, Track::Fraction(_))) {
+            // the item fits, or it can use the fractional tracks
+            continue;
+        }
+        let auto: Vec<usize> = range.filter(|&i| tracks[i] == Track::Auto).collect();
+        if !auto.is_empty() {
+            let excess = size - current;
+            let n = auto.len() as u32;
+            for (k, &i) in auto.iter().enumerate() {
+                sizes[i] += excess / n + u32::from((k as u32) < excess % n);
+            }
+        }
+    }
+
+    // fractions share the remaining space
+    let used = sizes.iter().sum::<u32>() + gap * (tracks.len() as u32).saturating_sub(1);
+    let free = length.saturating_sub(used);
+    let total: u32 = tracks
+        .iter()
+        .map(|track| match *track {
+            Track::Fraction(f) => f,
+            _ => 0,
+        })
+        .sum();
+    if total > 0 {
+        let mut given = 0;
+        for (size, track) in sizes.iter_mut().zip(tracks) {
+            if let Track::Fraction(f) = *track {
+                *size = (free as u64 * f as u64 / total as u64) as u32;
+                given += *size;
+            }
+        }
+        // the rounding leftover goes to the first fractional tracks
+        let mut left = free - given;
+        for (size, track) in sizes.iter_mut().zip(tracks) {
+            if left == 0 {
+                break;
+            }
+            if let Track::Fraction(f) = *track {
+                if f > 0 {
+                    *size += 1;
+                    left -= 1;
+                }
+            }
+        }
+    }
+
+    sizes
+}
+
+/// Gets the start and end coordinates of each track.
+fn track_offsets(sizes: &[u32], start: i32, gap: u32) -> Vec<(i32, i32)> {
+    let mut pos = start;
+    sizes
+        .iter()
+        .map(|&size| {
+            let range = (pos, pos + size as i32);
+            pos += (size + gap) as i32;
+            range
+        })
+        .collect()
+}
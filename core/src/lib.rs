@@ -2,7 +2,9 @@
 #[macro_use]
 mod macros;
 
+pub mod animation;
 pub mod backend;
+pub mod clipboard;
 pub mod draw;
 pub mod event;
 pub mod font;
@@ -11,6 +13,7 @@ pub mod image;
 pub mod layout;
 pub mod prelude;
 pub mod testing;
+pub mod timer;
 pub mod toplevel;
 pub mod visitor;
 pub mod widget;
@@ -1,20 +1,36 @@
-use crate::backend::{DrawBackend, Resources, TextureError};
-use crate::draw::{ColorOp, TexCoord, TextSection, TextureId};
+mod driver;
+pub use driver::*;
+mod metrics;
+pub use metrics::TEST_GLYPH_ADVANCE;
+mod software;
+pub use software::*;
+#[cfg(feature = "image")]
+mod snapshot;
+#[cfg(feature = "image")]
+pub use snapshot::*;
+
+use crate::backend::{Clipboard, DrawBackend, Resources, TextureError};
+use crate::clipboard::MemoryClipboard;
+use crate::draw::{ColorOp, GlyphPosition, TexCoord, TextSection, TextureId};
 use crate::font::{FontFamily, FontId, FontLoadError, FontProperties, FontSource};
 use crate::geometry::{Point, Rect};
 use crate::image::Image;
 pub use glyph_brush::OwnedSection;
+use metrics::layout_glyphs;
 use std::collections::HashMap;
 use std::ops::Add;
 
 /// Test backend implementation.
 ///
-/// It does nothing but storing the values it receives.
+/// It does nothing but storing the values it receives. Text is laid out with deterministic metrics,
+/// where every glyph is `TEST_GLYPH_ADVANCE` times the font scale wide and lines are as tall as the font scale.
+/// The clipboard is kept in memory, so tests can inspect what widgets copy.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TestBackend {
     pub textures: HashMap<TextureId, Image>,
     pub fonts: Vec<FontSource>,
     pub draw_cmd: Vec<TestDrawCmd>,
+    pub clipboard: MemoryClipboard,
 }
 
 impl Resources for TestBackend {
@@ -45,6 +61,28 @@ impl Resources for TestBackend {
         self.fonts.push(font_src.clone());
         Ok(FontId(id))
     }
+
+    fn measure_text(&mut self, text: &TextSection) -> Option<Rect> {
+        layout_glyphs(text)
+            .into_iter()
+            .map(|g| (g.pos, g.pos + g.size))
+            .reduce(|(min0, max0), (min1, max1)| {
+                (
+                    Point::new(min0.x.min(min1.x), min0.y.min(min1.y)),
+                    Point::new(max0.x.max(max1.x), max0.y.max(max1.y)),
+                )
+            })
+            .map(|(min, max)| Rect::enclosing(min, max))
+    }
+
+    fn glyph_positions(&mut self, text: &TextSection) -> Vec<GlyphPosition> {
+        layout_glyphs(text)
+    }
+
+    #[inline]
+    fn clipboard(&mut self) -> Option<&mut dyn Clipboard> {
+        Some(&mut self.clipboard)
+    }
 }
 
 impl DrawBackend for TestBackend {
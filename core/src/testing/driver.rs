@@ -0,0 +1,291 @@
+use crate::backend::Resources;
+use crate::event::{
+    Axis, ButtonState, Event, Key, KeyModState, MouseButton, MouseButtonsState, ScrollDelta, ScrollPhase, TouchId, TouchPhase,
+};
+use crate::geometry::Point;
+use crate::testing::TestBackend;
+use crate::timer;
+use crate::toplevel::TopLevel;
+use crate::widget::WidgetId;
+use std::time::{Duration, Instant};
+
+/// Number of intermediate motion events sent by `EventDriver::drag` and `EventDriver::touch_drag`.
+pub const DRAG_STEPS: u32 = 4;
+
+/// Virtual time between the motion events sent by `EventDriver::drag` and `EventDriver::touch_drag`.
+pub const DRAG_STEP_TIME: Duration = Duration::from_millis(16);
+
+/// Event simulation driver for tests.
+///
+/// Wraps a top level window and translates high level user actions into the sequence of events
+/// that a real backend would produce. Like the backends do, the window layout is updated after
+/// every consumed event.
+///
+/// Events are timestamped with a virtual clock that only moves forward with `advance`, so time
+/// dependant behavior like long presses doesn't depend on how fast the test runs.
+#[derive(Debug, Clone)]
+pub struct EventDriver<T, R = TestBackend> {
+    window: T,
+    resources: R,
+    pointer: Option<Point<f64>>,
+    buttons: MouseButtonsState,
+    mod_state: KeyModState,
+    clock: Instant,
+}
+
+impl<T: TopLevel> EventDriver<T> {
+    /// Creates a driver for the specified window, using a `TestBackend` as resources.
+    #[inline]
+    pub fn new(window: T) -> Self {
+        Self::with_resources(window, Default::default())
+    }
+}
+
+impl<T: TopLevel, R: Resources> EventDriver<T, R> {
+    /// Creates a driver for the specified window and resources.
+    pub fn with_resources(mut window: T, mut resources: R) -> Self {
+        window.update_layout(&mut resources);
+        EventDriver {
+            window,
+            resources,
+            pointer: None,
+            buttons: Default::default(),
+            mod_state: Default::default(),
+            clock: Instant::now(),
+        }
+    }
+
+    /// Gets the wrapped window.
+    #[inline]
+    pub fn window(&self) -> &T {
+        &self.window
+    }
+
+    /// Gets the wrapped window as mutable.
+    #[inline]
+    pub fn window_mut(&mut self) -> &mut T {
+        &mut self.window
+    }
+
+    /// Gets the resources used for layout.
+    #[inline]
+    pub fn resources(&mut self) -> &mut R {
+        &mut self.resources
+    }
+
+    /// Consumes the driver and returns the wrapped window.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.window
+    }
+
+    /// Gets the last pointer position, or `None` if the pointer is outside the window.
+    #[inline]
+    pub fn pointer_pos(&self) -> Option<Point<f64>> {
+        self.pointer
+    }
+
+    /// Gets the current keyboard modifier state.
+    #[inline]
+    pub fn mod_state(&self) -> KeyModState {
+        self.mod_state
+    }
+
+    /// Gets the current time of the virtual clock.
+    #[inline]
+    pub fn now(&self) -> Instant {
+        self.clock
+    }
+
+    /// Advances the virtual clock and delivers the timers and animation frames that expired meanwhile.
+    ///
+    /// Returns `true` if any of them was consumed.
+    pub fn advance(&mut self, duration: Duration) -> bool {
+        self.clock += duration;
+        self.process_timers(self.clock)
+    }
+
+    /// Sends a raw event to the window. Returns `true` if the event was consumed.
+    pub fn push_event(&mut self, event: Event) -> bool {
+        match event {
+            Event::MouseMoved(Axis::Position(pos)) => self.pointer = Some(pos),
+            Event::PointerInside(false) => self.pointer = None,
+            Event::MouseButton(ButtonState::Pressed, button) => self.buttons.set(button),
+            Event::MouseButton(ButtonState::Released, button) => self.buttons.unset(button),
+            Event::ModifiersChanged(mod_state) => self.mod_state = mod_state,
+            _ => (),
+        }
+        let consumed = self.window.push_event_at(event, self.clock);
+        if consumed {
+            self.window.update_layout(&mut self.resources);
+        }
+        consumed
+    }
+
+    /// Sends an event to a specific widget. Returns `true` if the event was consumed.
+    pub fn push_event_to(&mut self, target: WidgetId, event: Event) -> bool {
+        let consumed = self.window.push_event_to(target, event);
+        if consumed {
+            self.window.update_layout(&mut self.resources);
+        }
+        consumed
+    }
+
+    /// Delivers the timers and animation frames that have expired at the specified instant.
+    ///
+    /// See `rtk::timer::take_expired`.
+    pub fn process_timers(&mut self, now: Instant) -> bool {
+        timer::take_expired(now)
+            .into_iter()
+            .fold(false, |consumed, (target, event)| self.push_event_to(target, event) | consumed)
+    }
+
+    /// Moves the pointer to the specified position.
+    ///
+    /// If the pointer is outside the window, a `PointerInside(true)` event is sent first.
+    pub fn move_to(&mut self, pos: impl Into<Point<f64>>) -> bool {
+        let enter = self.pointer.is_none() && self.push_event(Event::PointerInside(true));
+        self.push_event(Event::MouseMoved(Axis::Position(pos.into()))) | enter
+    }
+
+    /// Moves the pointer outside the window.
+    pub fn leave(&mut self) -> bool {
+        self.pointer.is_some() && self.push_event(Event::PointerInside(false))
+    }
+
+    /// Presses a mouse button at the current pointer position.
+    pub fn press(&mut self, button: MouseButton) -> bool {
+        self.push_event(Event::MouseButton(ButtonState::Pressed, button))
+    }
+
+    /// Releases a mouse button at the current pointer position.
+    pub fn release(&mut self, button: MouseButton) -> bool {
+        self.push_event(Event::MouseButton(ButtonState::Released, button))
+    }
+
+    /// Moves the pointer and clicks a mouse button.
+    pub fn click_at(&mut self, pos: impl Into<Point<f64>>, button: MouseButton) -> bool {
+        self.move_to(pos) | self.press(button) | self.release(button)
+    }
+
+    /// Drags the pointer with the left mouse button from one position to another.
+    pub fn drag(&mut self, from: impl Into<Point<f64>>, to: impl Into<Point<f64>>) -> bool {
+        let from = from.into();
+        let to = to.into();
+        let mut consumed = self.move_to(from) | self.press(MouseButton::Left);
+        for i in 1..=DRAG_STEPS {
+            let pos = from.interpolate(to, i as f64 / DRAG_STEPS as f64);
+            consumed |= self.advance(DRAG_STEP_TIME) | self.move_to(pos);
+        }
+        consumed | self.release(MouseButton::Left)
+    }
+
+    /// Scrolls the mouse wheel by the specified amount of lines.
+    pub fn scroll(&mut self, dx: f32, dy: f32) -> bool {
+        self.push_event(Event::MouseMoved(Axis::Scroll(ScrollDelta::Lines(dx, dy), ScrollPhase::Moved)))
+    }
+
+    /// Scrolls by the specified amount of pixels, like a precise device (touchpad) does.
+    pub fn scroll_pixels(&mut self, dx: f64, dy: f64) -> bool {
+        self.push_event(Event::MouseMoved(Axis::Scroll(ScrollDelta::Pixels(dx, dy), ScrollPhase::Moved)))
+    }
+
+    /// Sends a touch event for the specified contact point.
+    pub fn touch(&mut self, id: TouchId, phase: TouchPhase, pos: impl Into<Point<f64>>) -> bool {
+        self.push_event(Event::Touch {
+            id,
+            phase,
+            pos: pos.into(),
+            force: None,
+        })
+    }
+
+    /// Touches the screen at `from`, moves the contact point to `to` and lifts it.
+    pub fn touch_drag(&mut self, id: TouchId, from: impl Into<Point<f64>>, to: impl Into<Point<f64>>) -> bool {
+        let from = from.into();
+        let to = to.into();
+        let mut consumed = self.touch(id, TouchPhase::Started, from);
+        for i in 1..=DRAG_STEPS {
+            let pos = from.interpolate(to, i as f64 / DRAG_STEPS as f64);
+            consumed |= self.advance(DRAG_STEP_TIME) | self.touch(id, TouchPhase::Moved, pos);
+        }
+        consumed | self.touch(id, TouchPhase::Ended, to)
+    }
+
+    /// Sets the keyboard modifier state, sending a `ModifiersChanged` event if it changed.
+    pub fn set_modifiers(&mut self, mod_state: KeyModState) -> bool {
+        self.mod_state != mod_state && self.push_event(Event::ModifiersChanged(mod_state))
+    }
+
+    /// Presses and releases a key with the specified modifiers active.
+    ///
+    /// The previous modifier state is restored afterwards. No `Character` event is produced.
+    pub fn press_key(&mut self, key: Key, mod_state: KeyModState) -> bool {
+        let prev_mods = self.mod_state;
+        let consumed = self.set_modifiers(mod_state)
+            | self.push_event(Event::Keyboard {
+                state: ButtonState::Pressed,
+                key,
+                scancode: 0,
+            })
+            | self.push_event(Event::Keyboard {
+                state: ButtonState::Released,
+                key,
+                scancode: 0,
+            });
+        consumed | self.set_modifiers(prev_mods)
+    }
+
+    /// Types some text, sending key press, `Character` and key release events for every char.
+    ///
+    /// Shift is held while typing uppercase letters.
+    pub fn type_text(&mut self, text: &str) -> bool {
+        let prev_mods = self.mod_state;
+        let mut consumed = false;
+        for c in text.chars() {
+            let key = key_for_char(c);
+            let shift = c.is_uppercase() || prev_mods.shift;
+            consumed |= self.set_modifiers(KeyModState { shift, ..prev_mods });
+            consumed |= self.push_event(Event::Keyboard {
+                state: ButtonState::Pressed,
+                key,
+                scancode: 0,
+            });
+            consumed |= self.push_event(Event::Character(c));
+            consumed |= self.push_event(Event::Keyboard {
+                state: ButtonState::Released,
+                key,
+                scancode: 0,
+            });
+        }
+        consumed | self.set_modifiers(prev_mods)
+    }
+}
+
+/// Gets the key that produces a character on an US keyboard layout.
+fn key_for_char(c: char) -> Key {
+    match c {
+        'a'..='z' | 'A'..='Z' => Key::Letter(c.to_ascii_lowercase()),
+        '0'..='9' => Key::Num(c as u8 - b'0', false),
+        ' ' => Key::Space,
+        '\n' | '\r' => Key::Enter(false),
+        '\t' => Key::Tab,
+        '\u{8}' => Key::BackSpace,
+        '\u{1b}' => Key::Escape,
+        '+' => Key::Plus(false),
+        '-' => Key::Minus(false),
+        '*' => Key::Multiply(false),
+        '/' => Key::Slash(false),
+        '\\' => Key::Backslash,
+        ',' => Key::Comma(false),
+        '.' => Key::Period,
+        ':' => Key::Colon,
+        ';' => Key::Semicolon,
+        '\'' => Key::Apostrophe,
+        '`' => Key::Grave,
+        '[' => Key::LBracket,
+        ']' => Key::RBracket,
+        '=' => Key::Equals(false),
+        _ => Key::Unk,
+    }
+}
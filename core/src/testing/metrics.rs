@@ -0,0 +1,107 @@
+use crate::draw::{GlyphPosition, TextLayout, TextSection};
+use crate::geometry::{AlignValue, Point};
+
+/// Glyph advance used by `TestBackend`, relative to the horizontal font scale.
+pub const TEST_GLYPH_ADVANCE: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy)]
+struct Glyph {
+    ch: char,
+    section_index: usize,
+    byte_index: usize,
+    advance: f32,
+    height: f32,
+}
+
+#[derive(Debug, Default)]
+struct Line {
+    glyphs: Vec<Glyph>,
+    width: f32,
+    height: f32,
+}
+
+impl Line {
+    fn push(&mut self, glyph: Glyph) {
+        self.width += glyph.advance;
+        self.height = self.height.max(glyph.height);
+        self.glyphs.push(glyph);
+    }
+
+    fn split_off(&mut self, at: usize) -> Line {
+        let mut rest = Line::default();
+        for glyph in self.glyphs.drain(at..) {
+            rest.push(glyph);
+        }
+        // the whitespace is measured before removing it, so a line made only of it keeps it's height
+        self.height = self.glyphs.iter().map(|g| g.height).fold(0.0, f32::max);
+        // whitespace at the break point doesn't take space on either line
+        while self.glyphs.last().is_some_and(|g| g.ch.is_whitespace()) {
+            self.glyphs.pop();
+        }
+        self.width = self.glyphs.iter().map(|g| g.advance).sum();
+        rest
+    }
+}
+
+/// Lays out a text section using fixed metrics.
+///
+/// Every glyph advances `scale.x * TEST_GLYPH_ADVANCE` pixels, and lines are as tall as the largest scale on them.
+/// Wrapping layouts break lines at whitespace (or anywhere if a word doesn't fit) when exceeding the section bounds.
+pub(crate) fn layout_glyphs(section: &TextSection) -> Vec<GlyphPosition> {
+    let (h_align, v_align, max_width, single_line) = match section.layout {
+        TextLayout::SingleLine { h_align, v_align, .. } => (h_align, v_align, f32::INFINITY, true),
+        TextLayout::Wrap { h_align, v_align, .. } => (h_align, v_align, section.bounds.0, false),
+    };
+
+    let mut lines = vec![Line::default()];
+    'outer: for (section_index, text) in section.text.iter().enumerate() {
+        let advance = text.scale.x * TEST_GLYPH_ADVANCE;
+        let height = text.scale.y;
+        for (byte_index, ch) in text.text.char_indices() {
+            let line = lines.last_mut().unwrap();
+            if ch == '\n' {
+                if single_line {
+                    break 'outer;
+                }
+                line.height = line.height.max(height);
+                lines.push(Line::default());
+                continue;
+            }
+            if !ch.is_whitespace() && !line.glyphs.is_empty() && line.width + advance > max_width {
+                let brk = line
+                    .glyphs
+                    .iter()
+                    .rposition(|g| g.ch.is_whitespace())
+                    .map_or(line.glyphs.len(), |i| i + 1);
+                let rest = line.split_off(brk);
+                lines.push(rest);
+            }
+            lines.last_mut().unwrap().push(Glyph {
+                ch,
+                section_index,
+                byte_index,
+                advance,
+                height,
+            });
+        }
+    }
+
+    let total_height: f32 = lines.iter().map(|l| l.height).sum();
+    let (x, y) = section.screen_position;
+    let mut line_y = y - total_height * v_align.value();
+    let mut glyphs = vec![];
+    for line in lines {
+        let mut glyph_x = x - line.width * h_align.value();
+        for glyph in line.glyphs {
+            glyphs.push(GlyphPosition {
+                section_index: glyph.section_index,
+                byte_index: glyph.byte_index,
+                pos: Point::new(glyph_x, line_y),
+                size: Point::new(glyph.advance, glyph.height),
+            });
+            glyph_x += glyph.advance;
+        }
+        line_y += line.height;
+    }
+    glyphs
+}
@@ -0,0 +1,199 @@
+use crate::geometry::Size;
+use crate::image::{Image, ImageData};
+use crate::testing::SoftwareBackend;
+use crate::toplevel::TopLevel;
+use image::{ColorType, ImageError};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Environment variable that makes snapshot checks overwrite the stored images.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "RTK_UPDATE_SNAPSHOTS";
+
+/// Golden image snapshot tester.
+///
+/// Renders a top level window at a fixed size with the `SoftwareBackend` and compares the result
+/// against a PNG image stored in the snapshot directory. On mismatch, the rendered image and a diff
+/// image are written next to the stored one (as `<name>.new.png` and `<name>.diff.png`).
+///
+/// If the `RTK_UPDATE_SNAPSHOTS` environment variable is set, the stored images are replaced instead.
+#[derive(Debug)]
+pub struct Snapshot {
+    dir: PathBuf,
+    tolerance: u8,
+    update: bool,
+    backend: SoftwareBackend,
+}
+
+impl Snapshot {
+    /// Creates a snapshot tester that renders windows at the specified size.
+    pub fn new(dir: impl Into<PathBuf>, size: impl Into<Size>) -> Self {
+        Self {
+            dir: dir.into(),
+            tolerance: 0,
+            update: update_requested(),
+            backend: SoftwareBackend::new(size),
+        }
+    }
+
+    /// Sets the maximum difference allowed on each color channel (in 8-bit sRGB units).
+    #[inline]
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets if the stored images are replaced instead of compared, overriding the environment variable.
+    #[inline]
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Gets the backend used for rendering.
+    #[inline]
+    pub fn backend(&mut self) -> &mut SoftwareBackend {
+        &mut self.backend
+    }
+
+    /// Lays out and draws a window at the snapshot size.
+    pub fn render<T: TopLevel>(&mut self, window: &mut T) -> Image {
+        let size = self.backend.get_size();
+        window.get_attr_mut().set_size(size);
+        window.update_layout(&mut self.backend);
+        self.backend.clear(window.get_attr().background.unwrap_or_default());
+        window.draw(&mut self.backend);
+        self.backend.to_image()
+    }
+
+    /// Renders a window and compares it against the stored snapshot with the specified name.
+    pub fn compare<T: TopLevel>(&mut self, name: &str, window: &mut T) -> Result<(), SnapshotError> {
+        let image = self.render(window);
+        let size = image.get_size();
+        let actual = rgba8_data(&image);
+        let path = self.path_for(name, "png");
+
+        if self.update {
+            std::fs::create_dir_all(&self.dir).map_err(ImageError::from)?;
+            image::save_buffer(&path, actual, size.w, size.h, ColorType::Rgba8)?;
+            return Ok(());
+        }
+
+        if !path.exists() {
+            return Err(SnapshotError::Missing(path));
+        }
+        let expected = image::open(&path)?.into_rgba8();
+        let expected_size = Size::from(expected.dimensions());
+        if expected_size != size {
+            return Err(SnapshotError::SizeMismatch {
+                expected: expected_size,
+                actual: size,
+            });
+        }
+
+        let mut pixels = 0;
+        let mut max_diff = 0;
+        let mut diff_img = Vec::with_capacity(actual.len());
+        for (a, e) in actual.chunks_exact(4).zip(expected.as_raw().chunks_exact(4)) {
+            let diff = a.iter().zip(e).map(|(&a, &e)| a.abs_diff(e)).max().unwrap_or(0);
+            max_diff = max_diff.max(diff);
+            if diff > self.tolerance {
+                pixels += 1;
+                diff_img.extend_from_slice(&[255, 0, 0, 255]);
+            } else {
+                // dimmed grayscale version of the expected pixel
+                let gray = ((e[0] as u32 + e[1] as u32 + e[2] as u32) / 9) as u8;
+                diff_img.extend_from_slice(&[gray, gray, gray, 255]);
+            }
+        }
+
+        if pixels == 0 {
+            return Ok(());
+        }
+        let new_path = self.path_for(name, "new.png");
+        let diff_path = self.path_for(name, "diff.png");
+        image::save_buffer(&new_path, actual, size.w, size.h, ColorType::Rgba8)?;
+        image::save_buffer(&diff_path, &diff_img, size.w, size.h, ColorType::Rgba8)?;
+
+        Err(SnapshotError::Mismatch {
+            pixels,
+            max_diff,
+            diff_path,
+        })
+    }
+
+    /// Renders a window and asserts it matches the stored snapshot with the specified name.
+    #[track_caller]
+    pub fn assert_matches<T: TopLevel>(&mut self, name: &str, window: &mut T) {
+        if let Err(err) = self.compare(name, window) {
+            panic!("snapshot `{}` failed: {}", name, err);
+        }
+    }
+
+    #[inline]
+    fn path_for(&self, name: &str, ext: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", name, ext))
+    }
+}
+
+/// Error produced by snapshot comparisons.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// There is no stored snapshot with that name.
+    Missing(PathBuf),
+    /// The stored snapshot has a different size.
+    SizeMismatch { expected: Size, actual: Size },
+    /// Some pixels differ more than the allowed tolerance.
+    Mismatch { pixels: usize, max_diff: u8, diff_path: PathBuf },
+    /// Error while reading or writing images.
+    Image(ImageError),
+}
+
+impl From<ImageError> for SnapshotError {
+    #[inline]
+    fn from(err: ImageError) -> Self {
+        Self::Image(err)
+    }
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing(path) => write!(
+                f,
+                "snapshot {} not found (set {} to create it)",
+                path.display(),
+                UPDATE_SNAPSHOTS_VAR
+            ),
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "size mismatch: expected {}x{}, got {}x{}",
+                expected.w, expected.h, actual.w, actual.h
+            ),
+            Self::Mismatch {
+                pixels,
+                max_diff,
+                diff_path,
+            } => write!(
+                f,
+                "{} pixels differ (max channel difference: {}), see {}",
+                pixels,
+                max_diff,
+                diff_path.display()
+            ),
+            Self::Image(err) => write!(f, "image error: {}", err),
+        }
+    }
+}
+
+/// Checks if the snapshots should be updated instead of compared.
+fn update_requested() -> bool {
+    std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some_and(|val| !val.is_empty() && val != "0")
+}
+
+/// Gets the pixel data of an image produced by `SoftwareBackend::to_image`.
+fn rgba8_data(image: &Image) -> &[u8] {
+    match image.get_data() {
+        Some(ImageData::U8(data)) => data,
+        _ => unreachable!("SoftwareBackend produces 8-bit images"),
+    }
+}
@@ -0,0 +1,470 @@
+use crate::backend::{DrawBackend, Resources, TextureError};
+use crate::draw::{srgb_to_linear, Color, ColorOp, FillMode, GlyphPosition, TexCoord, TextSection, TextureId};
+use crate::font::{FontFamily, FontId, FontLoadError, FontProperties, FontSource};
+use crate::geometry::{Point, Rect, Size};
+use crate::image::{Image, ImageData, PixelFormat};
+use crate::testing::TestVertex;
+use font_kit::source::SystemSource;
+use glyph_brush::ab_glyph::{self, FontVec};
+use glyph_brush::{BrushAction, BrushError, Extra, GlyphBrush, GlyphBrushBuilder, GlyphCruncher, GlyphVertex};
+use std::collections::{hash_map, HashMap};
+use std::fmt;
+
+/// Software rendering backend.
+///
+/// Rasterizes everything on the CPU into an RGBA pixel buffer, following the same rules
+/// as the OpenGL backends (pixel centers, nearest texture sampling, alpha blending in linear space).
+/// Useful for rendering windows headlessly.
+pub struct SoftwareBackend {
+    /// Size of the pixel buffer.
+    size: Size,
+    /// Pixel buffer (linear space).
+    pixels: Vec<Color>,
+    /// Maps user texture id's into pixel data.
+    textures: HashMap<TextureId, Texture>,
+    /// Used to find system fonts.
+    font_src: SystemSource,
+    /// Currently loaded fonts.
+    loaded_fonts: HashMap<FontSource, FontId>,
+    /// Text rendering engine.
+    glyph_brush: GlyphBrush<GlyphQuad, Extra, FontVec>,
+    /// Font texture cache (coverage values).
+    font_tex: FontTex,
+}
+
+impl fmt::Debug for SoftwareBackend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SoftwareBackend")
+            .field("size", &self.size)
+            .field("pixels", &format_args!("[...]"))
+            .field("textures", &self.textures.keys())
+            .field("font_src", &format_args!("..."))
+            .field("loaded_fonts", &self.loaded_fonts)
+            .field("glyph_brush", &self.glyph_brush)
+            .field("font_tex", &self.font_tex.size)
+            .finish()
+    }
+}
+
+impl SoftwareBackend {
+    /// Creates a new backend with a pixel buffer of the specified size.
+    ///
+    /// The buffer is initially transparent black. The system's default sans-serif font is loaded
+    /// as the first font (if available), so text sections with the default font id can be drawn.
+    pub fn new(size: impl Into<Size>) -> Self {
+        let size = size.into();
+        let glyph_brush = GlyphBrushBuilder::using_fonts(vec![]).cache_redraws(false).build();
+        let font_tex = FontTex::new(glyph_brush.texture_dimensions());
+
+        let mut this = Self {
+            size,
+            pixels: vec![Default::default(); size.area()],
+            textures: Default::default(),
+            font_src: SystemSource::new(),
+            loaded_fonts: Default::default(),
+            glyph_brush,
+            font_tex,
+        };
+
+        if let Some(default_font) = this.select_font(&[FontFamily::SansSerif], &Default::default()) {
+            let _ = this.load_font(&default_font);
+        }
+
+        this
+    }
+
+    /// Gets the size of the pixel buffer.
+    #[inline]
+    pub fn get_size(&self) -> Size {
+        self.size
+    }
+
+    /// Resizes the pixel buffer, clearing it's contents.
+    pub fn resize(&mut self, size: impl Into<Size>) {
+        self.size = size.into();
+        self.pixels.clear();
+        self.pixels.resize(self.size.area(), Default::default());
+    }
+
+    /// Fills the entire pixel buffer with a single color.
+    pub fn clear(&mut self, color: impl Into<Color>) {
+        let color = color.into();
+        self.pixels.iter_mut().for_each(|p| *p = color);
+    }
+
+    /// Gets the color of a pixel (in linear space).
+    #[inline]
+    pub fn get_pixel(&self, pos: impl Into<Point<i32>>) -> Option<Color> {
+        let pos = pos.into();
+        if pos.inside(self.size.into()) {
+            Some(self.pixels[pos.y as usize * self.size.w as usize + pos.x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Converts the pixel buffer into a 8-bit sRGBA image.
+    pub fn to_image(&self) -> Image {
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|c| {
+                let [r, g, b, _] = c.into_srgba8();
+                let a = (c.clamp().a * 255.0).round() as u8;
+                vec![r, g, b, a]
+            })
+            .collect();
+        Image::new(data, self.size, PixelFormat::Rgba)
+    }
+
+    /// Blends a color value into a pixel.
+    #[inline]
+    fn blend(&mut self, x: i32, y: i32, src: Color) {
+        let dst = &mut self.pixels[y as usize * self.size.w as usize + x as usize];
+        let src = src.clamp();
+        *dst = src * src.a + *dst * (1.0 - src.a);
+    }
+
+    /// Calculates the drawable area inside a viewport.
+    #[inline]
+    fn clip_area(&self, viewport: Rect) -> Option<Rect> {
+        viewport.clip_inside(self.size.into())
+    }
+
+    /// Rasterizes a single triangle.
+    fn fill_triangle(&mut self, verts: [TestVertex; 3], texture: Option<TextureId>, clip: Rect) {
+        let [v0, mut v1, mut v2] = verts;
+        let mut area = edge(v0.pos, v1.pos, v2.pos);
+        if area == 0.0 {
+            return;
+        }
+        if area < 0.0 {
+            std::mem::swap(&mut v1, &mut v2);
+            area = -area;
+        }
+
+        // bounding box of the triangle, clipped against the viewport
+        let min_x = v0.pos.x.min(v1.pos.x).min(v2.pos.x).floor() as i32;
+        let min_y = v0.pos.y.min(v1.pos.y).min(v2.pos.y).floor() as i32;
+        let max_x = v0.pos.x.max(v1.pos.x).max(v2.pos.x).ceil() as i32;
+        let max_y = v0.pos.y.max(v1.pos.y).max(v2.pos.y).ceil() as i32;
+        let x0 = min_x.max(clip.x());
+        let y0 = min_y.max(clip.y());
+        let x1 = max_x.min(clip.end_x());
+        let y1 = max_y.min(clip.end_y());
+
+        let tl0 = is_top_left(v1.pos, v2.pos);
+        let tl1 = is_top_left(v2.pos, v0.pos);
+        let tl2 = is_top_left(v0.pos, v1.pos);
+
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                // sample at the pixel center
+                let p = Point::new(x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = edge(v1.pos, v2.pos, p);
+                let w1 = edge(v2.pos, v0.pos, p);
+                let w2 = edge(v0.pos, v1.pos, p);
+                if covers(w0, tl0) && covers(w1, tl1) && covers(w2, tl2) {
+                    let (l0, l1, l2) = (w0 / area, w1 / area, w2 / area);
+                    let color = ColorOp {
+                        mul: v0.color.mul * l0 + v1.color.mul * l1 + v2.color.mul * l2,
+                        add: v0.color.add * l0 + v1.color.add * l1 + v2.color.add * l2,
+                    };
+                    let texc = v0.texc * l0 + v1.texc * l1 + v2.texc * l2;
+                    let texel = self.sample(texture, texc);
+                    self.blend(x, y, texel * color.mul + color.add);
+                }
+            }
+        }
+    }
+
+    /// Samples a texture using nearest filtering and repeat wrapping.
+    #[inline]
+    fn sample(&self, texture: Option<TextureId>, texc: TexCoord) -> Color {
+        texture
+            .and_then(|id| self.textures.get(&id))
+            .map_or(Color::WHITE, |tex| tex.sample(texc))
+    }
+
+    /// Rasterizes the glyph quads produced by the text engine.
+    fn fill_glyphs(&mut self, quads: Vec<GlyphQuad>, clip: Rect) {
+        for quad in quads {
+            let x0 = (quad.pixel.min.x.round() as i32).max(clip.x());
+            let y0 = (quad.pixel.min.y.round() as i32).max(clip.y());
+            let x1 = (quad.pixel.max.x.round() as i32 - 1).min(clip.end_x());
+            let y1 = (quad.pixel.max.y.round() as i32 - 1).min(clip.end_y());
+            let pw = quad.pixel.width();
+            let ph = quad.pixel.height();
+            if pw <= 0.0 || ph <= 0.0 {
+                continue;
+            }
+            let color = Color::from(quad.color);
+
+            for y in y0..=y1 {
+                let fy = (y as f32 + 0.5 - quad.pixel.min.y) / ph;
+                let v = quad.tex.min.y + fy * quad.tex.height();
+                for x in x0..=x1 {
+                    let fx = (x as f32 + 0.5 - quad.pixel.min.x) / pw;
+                    let u = quad.tex.min.x + fx * quad.tex.width();
+                    let coverage = self.font_tex.sample(u, v);
+                    if coverage > 0.0 {
+                        self.blend(x, y, color.with_alpha(color.a * coverage));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Runs the text engine on the queued sections.
+    fn process_text(&mut self) -> Vec<GlyphQuad> {
+        let font_tex = &mut self.font_tex;
+        let action = self
+            .glyph_brush
+            .process_queued(|rect, data| font_tex.update(rect, data), |gvert| gvert.into());
+        match action {
+            Ok(BrushAction::Draw(quads)) => quads,
+            Ok(BrushAction::ReDraw) => unreachable!(), // redraw caching is disabled
+            Err(BrushError::TextureTooSmall { suggested }) => {
+                self.font_tex = FontTex::new(suggested);
+                self.glyph_brush.resize_texture(suggested.0, suggested.1);
+                self.process_text()
+            }
+        }
+    }
+}
+
+impl Resources for SoftwareBackend {
+    fn load_texture(&mut self, id: TextureId, image: &Image) -> Result<(), TextureError> {
+        self.textures.insert(id, Texture::from_image(image));
+        Ok(())
+    }
+
+    fn load_texture_once(&mut self, id: TextureId, image: &Image) -> Result<(), TextureError> {
+        if let hash_map::Entry::Vacant(entry) = self.textures.entry(id) {
+            entry.insert(Texture::from_image(image));
+        }
+        Ok(())
+    }
+
+    fn delete_texture(&mut self, id: TextureId) {
+        self.textures.remove(&id);
+    }
+
+    fn enumerate_fonts(&self) -> Vec<String> {
+        self.font_src.all_families().unwrap_or_default()
+    }
+
+    fn select_font(&self, family_names: &[FontFamily], properties: &FontProperties) -> Option<FontSource> {
+        match self.font_src.select_best_match(family_names, properties) {
+            Ok(font_kit::handle::Handle::Path { path, font_index }) => Some(FontSource { path, font_index }),
+            _ => None,
+        }
+    }
+
+    fn load_font(&mut self, font_src: &FontSource) -> Result<FontId, FontLoadError> {
+        if let Some(font_id) = self.loaded_fonts.get(font_src) {
+            Ok(*font_id)
+        } else {
+            let data = std::fs::read(&font_src.path)?;
+            let font = FontVec::try_from_vec_and_index(data, font_src.font_index).map_err(|_| FontLoadError::InvalidData)?;
+            let id = self.glyph_brush.add_font(font);
+            self.loaded_fonts.insert(font_src.clone(), id);
+            Ok(id)
+        }
+    }
+
+    fn measure_text(&mut self, text: &TextSection) -> Option<Rect> {
+        if self.loaded_fonts.is_empty() {
+            return None;
+        }
+        self.glyph_brush
+            .glyph_bounds(text)
+            .map(|b| Rect::enclosing(Point::new(b.min.x, b.min.y), Point::new(b.max.x, b.max.y)))
+    }
+
+    fn glyph_positions(&mut self, text: &TextSection) -> Vec<GlyphPosition> {
+        if self.loaded_fonts.is_empty() {
+            return vec![];
+        }
+        let glyphs: Vec<_> = self.glyph_brush.glyphs(text).cloned().collect();
+        let fonts = self.glyph_brush.fonts();
+        glyphs
+            .iter()
+            .map(|g| GlyphPosition::from_section_glyph(g, &fonts[g.font_id.0]))
+            .collect()
+    }
+}
+
+impl DrawBackend for SoftwareBackend {
+    type Vertex = TestVertex;
+
+    fn draw_triangles<V, I>(&mut self, vertices: V, indices: I, texture: Option<TextureId>, viewport: Rect)
+    where
+        V: IntoIterator<Item = Self::Vertex>,
+        I: IntoIterator<Item = u32>,
+    {
+        let clip = if let Some(clip) = self.clip_area(viewport) { clip } else { return };
+        let vertices: Vec<_> = vertices.into_iter().collect();
+        let indices: Vec<_> = indices.into_iter().collect();
+        for tri in indices.chunks_exact(3) {
+            let verts = [vertices[tri[0] as usize], vertices[tri[1] as usize], vertices[tri[2] as usize]];
+            self.fill_triangle(verts, texture, clip);
+        }
+    }
+
+    fn draw_text(&mut self, text: TextSection, viewport: Rect) {
+        if self.loaded_fonts.is_empty() {
+            return;
+        }
+        let clip = if let Some(clip) = self.clip_area(viewport) { clip } else { return };
+        self.glyph_brush.queue(text);
+        let quads = self.process_text();
+        self.fill_glyphs(quads, clip);
+    }
+
+    fn draw_rect(&mut self, rect: Rect, fill: FillMode, viewport: Rect) {
+        if rect.size.is_zero_area() {
+            return;
+        }
+        let clip = if let Some(clip) = self.clip_area(viewport).and_then(|vp| rect.clip_inside(vp)) {
+            clip
+        } else {
+            return;
+        };
+        let color = fill.color();
+        let texr = fill.texrect();
+        let texture = fill.texture();
+        let size: Point<f32> = rect.size.as_point();
+
+        for y in clip.y()..=clip.end_y() {
+            let fy = (y - rect.y()) as f32 + 0.5;
+            for x in clip.x()..=clip.end_x() {
+                let fx = (x - rect.x()) as f32 + 0.5;
+                let t = TexCoord::new(fx / size.x, fy / size.y);
+                let texc = texr.top_left + (texr.bot_right - texr.top_left) * t;
+                let texel = self.sample(texture, texc);
+                self.blend(x, y, texel * color.mul + color.add);
+            }
+        }
+    }
+}
+
+/// Texture pixel data stored in linear space.
+#[derive(Debug, Clone)]
+struct Texture {
+    size: Size,
+    pixels: Vec<Color>,
+}
+
+impl Texture {
+    fn from_image(image: &Image) -> Self {
+        let size = image.get_size();
+        let format = image.get_format();
+        let pixels = match image.get_data() {
+            Some(ImageData::U8(data)) => to_colors(data, format, |v| v as f32 / 255.0),
+            Some(ImageData::U16(data)) => to_colors(data, format, |v| v as f32 / 65535.0),
+            Some(ImageData::U32(data)) => to_colors(data, format, |v| v as f32 / u32::MAX as f32),
+            Some(ImageData::F32(data)) => to_colors(data, format, |v| v),
+            None => vec![Default::default(); size.area()],
+        };
+        Self { size, pixels }
+    }
+
+    #[inline]
+    fn sample(&self, texc: TexCoord) -> Color {
+        if self.size.is_zero_area() {
+            return Default::default();
+        }
+        let x = ((texc.u - texc.u.floor()) * self.size.w as f32) as usize;
+        let y = ((texc.v - texc.v.floor()) * self.size.h as f32) as usize;
+        let x = x.min(self.size.w as usize - 1);
+        let y = y.min(self.size.h as usize - 1);
+        self.pixels[y * self.size.w as usize + x]
+    }
+}
+
+/// Converts sRGB encoded pixel components into linear colors.
+fn to_colors<T: Copy>(data: &[T], format: PixelFormat, norm: impl Fn(T) -> f32) -> Vec<Color> {
+    data.chunks_exact(format.num_components())
+        .map(|px| {
+            let c = |i: usize| srgb_to_linear(norm(px[i]));
+            match format {
+                PixelFormat::Luma => Color::gray(c(0)),
+                PixelFormat::LumaA => Color::gray(c(0)).with_alpha(norm(px[1])),
+                PixelFormat::Rgb => Color::rgb(c(0), c(1), c(2)),
+                PixelFormat::Rgba => Color::rgba(c(0), c(1), c(2), norm(px[3])),
+            }
+        })
+        .collect()
+}
+
+/// Glyph coverage texture used by the text engine.
+#[derive(Debug, Clone)]
+struct FontTex {
+    size: Size,
+    data: Vec<u8>,
+}
+
+impl FontTex {
+    #[inline]
+    fn new(size: impl Into<Size>) -> Self {
+        let size = size.into();
+        Self {
+            size,
+            data: vec![0; size.area()],
+        }
+    }
+
+    fn update(&mut self, rect: glyph_brush::Rectangle<u32>, data: &[u8]) {
+        let width = rect.width() as usize;
+        for (row, src) in data.chunks_exact(width).enumerate() {
+            let start = (rect.min[1] as usize + row) * self.size.w as usize + rect.min[0] as usize;
+            self.data[start..start + width].copy_from_slice(src);
+        }
+    }
+
+    #[inline]
+    fn sample(&self, u: f32, v: f32) -> f32 {
+        let x = ((u * self.size.w as f32) as usize).min(self.size.w as usize - 1);
+        let y = ((v * self.size.h as f32) as usize).min(self.size.h as usize - 1);
+        self.data[y * self.size.w as usize + x] as f32 / 255.0
+    }
+}
+
+/// Single glyph produced by the text engine.
+#[derive(Debug, Clone, Copy)]
+struct GlyphQuad {
+    pixel: ab_glyph::Rect,
+    tex: ab_glyph::Rect,
+    color: [f32; 4],
+}
+
+impl From<GlyphVertex<'_>> for GlyphQuad {
+    #[inline]
+    fn from(vert: GlyphVertex) -> Self {
+        Self {
+            pixel: vert.pixel_coords,
+            tex: vert.tex_coords,
+            color: vert.extra.color,
+        }
+    }
+}
+
+/// Edge function (twice the signed area of the triangle `a, b, p`).
+#[inline]
+fn edge(a: Point<f32>, b: Point<f32>, p: Point<f32>) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Checks if the edge `a -> b` is a top or left edge (used for the fill rule).
+#[inline]
+fn is_top_left(a: Point<f32>, b: Point<f32>) -> bool {
+    let d = b - a;
+    (d.y == 0.0 && d.x > 0.0) || d.y < 0.0
+}
+
+/// Checks if a pixel center is covered by an edge.
+#[inline]
+fn covers(w: f32, top_left: bool) -> bool {
+    w > 0.0 || (w == 0.0 && top_left)
+}
@@ -0,0 +1,158 @@
+//! Timers and animation frame requests.
+//!
+//! The timer queue is thread local, so widgets can schedule timers from their event handlers
+//! without having access to the main loop. The backend main loop is responsible for delivering
+//! the expired timers (see `take_expired`) to the target widgets.
+use crate::event::Event;
+use crate::widget::WidgetId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static TIMER_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Minimum time between two animation frames (60 frames per second).
+pub const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+thread_local! {
+    static TIMERS: RefCell<TimerQueue> = RefCell::new(Default::default());
+}
+
+/// Unique timer id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerId(usize);
+
+impl TimerId {
+    #[inline]
+    fn new() -> Self {
+        TimerId(TIMER_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Timer {
+    target: WidgetId,
+    deadline: Instant,
+    interval: Option<Duration>,
+}
+
+/// Pending timers and animation frame requests.
+#[derive(Debug, Default)]
+struct TimerQueue {
+    timers: HashMap<TimerId, Timer>,
+    frames: HashMap<WidgetId, Instant>,
+    last_frame: Option<Instant>,
+}
+
+/// Schedules a `Timer` event to be sent to the target widget once after the specified delay.
+pub fn set_timeout(target: WidgetId, delay: Duration) -> TimerId {
+    add_timer(target, delay, None)
+}
+
+/// Schedules a `Timer` event to be sent to the target widget repeatedly at the specified interval.
+pub fn set_interval(target: WidgetId, interval: Duration) -> TimerId {
+    add_timer(target, interval, Some(interval))
+}
+
+/// Schedules a `Timer` event to be sent to the target widget once at the specified instant.
+///
+/// Useful when the delay is relative to an event timestamp instead of the current time.
+pub fn set_timeout_at(target: WidgetId, deadline: Instant) -> TimerId {
+    insert_timer(target, deadline, None)
+}
+
+fn add_timer(target: WidgetId, delay: Duration, interval: Option<Duration>) -> TimerId {
+    insert_timer(target, Instant::now() + delay, interval)
+}
+
+fn insert_timer(target: WidgetId, deadline: Instant, interval: Option<Duration>) -> TimerId {
+    let id = TimerId::new();
+    let timer = Timer {
+        target,
+        deadline,
+        interval,
+    };
+    TIMERS.with(|q| q.borrow_mut().timers.insert(id, timer));
+    id
+}
+
+/// Cancels a timer. Returns `false` if the timer wasn't active.
+pub fn cancel_timer(id: TimerId) -> bool {
+    TIMERS.with(|q| q.borrow_mut().timers.remove(&id).is_some())
+}
+
+/// Requests an `AnimationFrame` event to be sent to the target widget on the next frame.
+///
+/// The request is fulfilled only once, widgets that keep animating must request a new frame every time.
+pub fn request_animation_frame(target: WidgetId) {
+    TIMERS.with(|q| {
+        q.borrow_mut().frames.entry(target).or_insert_with(Instant::now);
+    });
+}
+
+/// Checks if there are pending animation frame requests.
+pub fn animation_pending() -> bool {
+    TIMERS.with(|q| !q.borrow().frames.is_empty())
+}
+
+/// Gets the time when the next timer expires or the next animation frame is due.
+///
+/// Animation frames are spaced by at least `FRAME_INTERVAL`.
+pub fn next_deadline() -> Option<Instant> {
+    TIMERS.with(|q| {
+        let q = q.borrow();
+        let next_frame = q.frames.values().min().map(|&requested| match q.last_frame {
+            Some(last) => requested.max(last + FRAME_INTERVAL),
+            None => requested,
+        });
+        q.timers.values().map(|t| t.deadline).chain(next_frame).min()
+    })
+}
+
+/// Removes the expired timers and pending animation frames, returning the events that should be delivered.
+///
+/// Repeating timers are rescheduled. The returned events are sorted by deadline, with animation frames last.
+/// Pending animation frames are kept until `FRAME_INTERVAL` has passed since the last frame.
+pub fn take_expired(now: Instant) -> Vec<(WidgetId, Event)> {
+    TIMERS.with(|q| {
+        let mut q = q.borrow_mut();
+        let mut expired: Vec<_> = q
+            .timers
+            .iter()
+            .filter(|(_, timer)| timer.deadline <= now)
+            .map(|(&id, timer)| (timer.deadline, id, timer.target))
+            .collect();
+        expired.sort_unstable();
+
+        for &(_, id, _) in &expired {
+            let timer = q.timers.get_mut(&id).unwrap();
+            if let Some(interval) = timer.interval {
+                // skip the missed ticks instead of sending them all at once
+                timer.deadline += interval;
+                if timer.deadline <= now {
+                    timer.deadline = now + interval;
+                }
+            } else {
+                q.timers.remove(&id);
+            }
+        }
+
+        let frame_due = q.last_frame.is_none_or(|last| now >= last + FRAME_INTERVAL);
+        let mut frames: Vec<_> = if frame_due { q.frames.drain().collect() } else { vec![] };
+        if !frames.is_empty() {
+            q.last_frame = Some(now);
+        }
+        frames.sort_unstable_by_key(|&(target, requested)| (requested, target));
+
+        expired
+            .into_iter()
+            .map(|(_, id, target)| (target, Event::Timer(id)))
+            .chain(
+                frames
+                    .into_iter()
+                    .map(|(target, requested)| (target, Event::AnimationFrame(now.saturating_duration_since(requested)))),
+            )
+            .collect()
+    })
+}
@@ -4,6 +4,9 @@ pub use window::*;
 
 use crate::backend::{DrawBackend, Resources};
 use crate::event::Event;
+use crate::geometry::Rect;
+use crate::widget::WidgetId;
+use std::time::Instant;
 
 /// Defines an object that can be a top level window.
 pub trait TopLevel {
@@ -13,6 +16,32 @@ pub trait TopLevel {
 
     fn push_event(&mut self, event: Event) -> bool;
 
+    /// Sends an event that was received at the specified instant.
+    ///
+    /// The default implementation ignores the timestamp.
+    #[allow(unused_variables)]
+    #[inline]
+    fn push_event_at(&mut self, event: Event, timestamp: Instant) -> bool {
+        self.push_event(event)
+    }
+
+    /// Sends an event directly to the widget with the specified id.
+    ///
+    /// Returns `false` if the event wasn't consumed. The default implementation doesn't support targeted events.
+    #[allow(unused_variables)]
+    #[inline]
+    fn push_event_to(&mut self, target: WidgetId, event: Event) -> bool {
+        false
+    }
+
+    /// Gets the input method cursor area of the focused widget, relative to the window.
+    ///
+    /// The default implementation doesn't report any area.
+    #[inline]
+    fn ime_cursor_area(&mut self) -> Option<Rect> {
+        None
+    }
+
     fn get_attr(&self) -> &WindowAttributes;
 
     fn get_attr_mut(&mut self) -> &mut WindowAttributes;
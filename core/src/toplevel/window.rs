@@ -1,10 +1,11 @@
 use crate::backend::{DrawBackend, Resources};
 use crate::draw::{Color, DrawContext};
 use crate::event::{Event, EventDispatcher};
-use crate::geometry::{Position, Size};
+use crate::geometry::{Position, Rect, Size};
 use crate::toplevel::TopLevel;
-use crate::widget::Widget;
+use crate::widget::{Widget, WidgetId};
 use std::ops;
+use std::time::Instant;
 
 pub const DEFAULT_WINDOW_SIZE: Size = Size::new(320, 240);
 
@@ -37,28 +38,72 @@ impl<T> Window<T> {
             child,
         }
     }
+
+    /// Gets the event dispatcher.
+    #[inline]
+    pub fn dispatcher(&self) -> &EventDispatcher {
+        &self.dispatcher
+    }
+
+    /// Gets the event dispatcher as mutable.
+    #[inline]
+    pub fn dispatcher_mut(&mut self) -> &mut EventDispatcher {
+        &mut self.dispatcher
+    }
+
+    /// Gets the widget that has keyboard focus.
+    #[inline]
+    pub fn focused(&self) -> Option<WidgetId> {
+        self.dispatcher.focused()
+    }
+
+    /// Gets the widget that has grabbed the pointer.
+    #[inline]
+    pub fn grabbed(&self) -> Option<WidgetId> {
+        self.dispatcher.grabbed()
+    }
+
+    /// Releases the pointer grab before all mouse buttons are released.
+    #[inline]
+    pub fn release_grab(&mut self) {
+        self.dispatcher.release_grab()
+    }
+}
+
+impl<T: Widget> Window<T> {
+    /// Moves the keyboard focus to the specified widget, or removes focus if `None`.
+    #[inline]
+    pub fn set_focus(&mut self, target: Option<WidgetId>) -> bool {
+        self.dispatcher.set_focus(target, &mut self.child)
+    }
+
+    /// Moves the keyboard focus to the next widget, or the previous one if `reverse` is set.
+    #[inline]
+    pub fn focus_next(&mut self, reverse: bool) -> bool {
+        self.dispatcher.focus_next(reverse, &mut self.child)
+    }
 }
 
 impl<T: Widget> TopLevel for Window<T> {
     fn update_layout<R: Resources>(&mut self, resources: &mut R) {
+        // the child keeps it's position, the space before it is unused
+        let pos = self.child.get_position();
+        let offset = Size::new(pos.x.max(0) as u32, pos.y.max(0) as u32);
+
         if self.size.is_zero_area() {
-            // our size is unset, first try to get the default content size
-            let initial = self
-                .child
-                .get_bounds()
-                .expand_to_origin()
-                .map_size(|s| s.nonzero_or(DEFAULT_WINDOW_SIZE)); // if we failed to get a size then use a default
-
-            // update the child's size using this size as our viewport
-            self.child.update_layout(initial, resources);
-
-            // set our size to the calculated content size
-            let updated = self.child.get_bounds().expand_to_origin().size.nonzero_or(DEFAULT_WINDOW_SIZE);
-            self.set_size(updated);
-        } else {
-            // we alread have a size, only update child
-            self.child.update_layout(self.size.into(), resources);
+            // our size is unset, use the natural size of the content
+            let max_size = self.max_size.nonzero_or(Size::unbounded());
+            let hint = self.child.measure(max_size.saturating_sub(offset), resources);
+            let size = hint
+                .preferred
+                .saturating_add(offset)
+                .map2(max_size, u32::min)
+                .map2(self.min_size, u32::max)
+                .nonzero_or(DEFAULT_WINDOW_SIZE); // if we failed to get a size then use a default
+            self.set_size(size);
         }
+
+        self.child.arrange(Rect::new(pos, self.size.saturating_sub(offset)), resources);
     }
 
     fn draw<B: DrawBackend>(&self, backend: &mut B) {
@@ -70,6 +115,19 @@ impl<T: Widget> TopLevel for Window<T> {
         self.dispatcher.dispatch_event(event, self.size, &mut self.child)
     }
 
+    fn push_event_at(&mut self, event: Event, timestamp: Instant) -> bool {
+        self.dispatcher.dispatch_event_at(event, timestamp, self.size, &mut self.child)
+    }
+
+    fn push_event_to(&mut self, target: WidgetId, event: Event) -> bool {
+        self.dispatcher.dispatch_to(target, event, &mut self.child)
+    }
+
+    #[inline]
+    fn ime_cursor_area(&mut self) -> Option<Rect> {
+        self.dispatcher.ime_cursor_area(&mut self.child)
+    }
+
     #[inline]
     fn get_attr(&self) -> &WindowAttributes {
         &self.attr
@@ -5,7 +5,7 @@ pub use id::*;
 use crate::backend::{DrawBackend, Resources};
 use crate::draw::DrawContext;
 use crate::event::{Event, EventContext, EventResult};
-use crate::geometry::{Bounds, Position, Rect};
+use crate::geometry::{Bounds, Position, Rect, Size, SizeHint};
 use crate::visitor::Visitable;
 
 /// Defines an object that can be drawn and viewed inside a window.
@@ -13,6 +13,26 @@ pub trait Widget: ObjectId + Bounds + Visitable {
     /// Update the object's layout.
     fn update_layout<R: Resources>(&mut self, parent_rect: Rect, resources: &mut R);
 
+    /// Measures the size requirements of this widget, given the space available in the parent.
+    ///
+    /// This is the first layout pass: containers measure their children to decide where to place them with `arrange`.
+    /// The default implementation runs `update_layout` with the available space, and reports the resulting size as fixed.
+    fn measure<R: Resources>(&mut self, avail: Size, resources: &mut R) -> SizeHint {
+        self.update_layout(Rect::new_at_origin(avail), resources);
+        SizeHint::fixed(self.get_size())
+    }
+
+    /// Places this widget inside a rectangle of the parent and updates it's layout.
+    ///
+    /// This is the second layout pass, after `measure`. The widget takes the space it needs inside the rectangle.
+    /// The default implementation can't move the widget, it only runs `update_layout` with the rectangle size
+    /// available at the current position.
+    fn arrange<R: Resources>(&mut self, rect: Rect, resources: &mut R) {
+        let pos = self.get_position();
+        let offset = Size::new(pos.x.max(0) as u32, pos.y.max(0) as u32);
+        self.update_layout(Rect::new_at_origin(rect.size.saturating_add(offset)), resources);
+    }
+
     /// Draws the contents of this object.
     //TODO: invalidate mechanics to avoid overdraw
     fn draw<B: DrawBackend>(&self, dc: DrawContext<B>);
@@ -20,6 +40,15 @@ pub trait Widget: ObjectId + Bounds + Visitable {
     /// Handles an event sent to this widget.
     fn handle_event(&mut self, event: &Event, ctx: EventContext) -> EventResult;
 
+    /// Handles an event going to one of this widget's descendants, before the descendant receives it.
+    ///
+    /// Consuming the event here stops it from reaching the target.
+    /// The default implementation returns `EventResult::Pass`.
+    #[allow(unused_variables)]
+    fn capture_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        EventResult::Pass
+    }
+
     /// Event consumed notification.
     fn event_consumed(&mut self, event: &Event, ctx: &EventContext);
 
@@ -36,6 +65,28 @@ pub trait Widget: ObjectId + Bounds + Visitable {
     fn is_clipped(&self) -> bool {
         true
     }
+
+    /// Indicates if this widget can receive keyboard focus with the Tab key.
+    ///
+    /// The default implementation returns `false`.
+    fn accepts_focus(&self) -> bool {
+        false
+    }
+
+    /// Area of the text caret while this widget has keyboard focus, relative to the widget's top-left.
+    ///
+    /// The backend uses it to place the input method candidate window. The default implementation returns `None`.
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        None
+    }
+
+    /// Position of this widget in the focus traversal order.
+    ///
+    /// Widgets with a positive index are visited first in ascending order, then the ones with
+    /// index zero in tree order. The default implementation returns `0`.
+    fn tab_index(&self) -> u32 {
+        0
+    }
 }
 
 impl Widget for () {
@@ -0,0 +1,139 @@
+use rtk::animation::{Animated, Easing, Interpolate};
+use rtk::draw::ColorOp;
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::timer;
+use rtk::toplevel::Window;
+use rtk_derive::{Bounds, ObjectId, Visitable};
+use std::time::{Duration, Instant};
+
+/// Widget that fades its color when hovered.
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+struct Fader {
+    id: WidgetId,
+    bounds: Rect,
+    color: Animated<Color>,
+    frames: usize,
+}
+
+impl Widget for Fader {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn draw<B: DrawBackend>(&self, mut dc: DrawContext<B>) {
+        dc.fill(*self.color.get());
+    }
+
+    fn handle_event(&mut self, event: &Event, _ctx: EventContext) -> EventResult {
+        match event {
+            Event::PointerInside(inside) => {
+                let color = if *inside { Color::WHITE } else { Color::BLACK };
+                self.color.animate_to(color, self.id);
+                EventResult::Consumed
+            }
+            Event::AnimationFrame(elapsed) => {
+                self.frames += 1;
+                if self.color.tick(*elapsed, self.id) {
+                    EventResult::Consumed
+                } else {
+                    EventResult::Pass
+                }
+            }
+            _ => EventResult::Pass,
+        }
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+}
+
+#[test]
+fn interpolate_values() {
+    assert_near(2.0f32.interpolate(&4.0, 0.25), 2.5);
+    assert_eq!(Position::new(0, 10).interpolate(&Position::new(10, -10), 0.5), Position::new(5, 0));
+    assert_eq!(
+        Rect::new([0, 0], [10, 10]).interpolate(&Rect::new([10, 20], [30, 10]), 0.5),
+        Rect::new([5, 10], [20, 10])
+    );
+    assert_eq!(Color::BLACK.interpolate(&Color::WHITE, 0.5), Color::gray(0.5));
+    let op = ColorOp::mul(Color::WHITE).interpolate(&ColorOp::mul(Color::BLACK), 1.0);
+    assert_eq!(op, ColorOp::mul(Color::BLACK));
+}
+
+#[test]
+fn easing_curves() {
+    for &easing in &[
+        Easing::Linear,
+        Easing::EASE,
+        Easing::EASE_IN,
+        Easing::EASE_OUT,
+        Easing::EASE_IN_OUT,
+        Easing::SPRING,
+    ] {
+        assert_eq!(easing.apply(0.0), 0.0);
+        assert_eq!(easing.apply(1.0), 1.0);
+    }
+    assert_near(Easing::Linear.apply(0.3), 0.3);
+    assert_near(Easing::EASE_IN_OUT.apply(0.5), 0.5);
+    assert!(Easing::EASE_IN.apply(0.25) < 0.25);
+    assert!(Easing::EASE_OUT.apply(0.25) > 0.25);
+    // the spring overshoots the target
+    assert!((0..100).any(|i| Easing::SPRING.apply(i as f32 / 100.0) > 1.0));
+}
+
+#[test]
+fn animated_advance() {
+    let id = WidgetId::new();
+    let mut value = Animated::new(0.0f32, Duration::from_millis(100), Easing::Linear);
+    assert!(!value.is_running());
+
+    value.animate_to(10.0, id);
+    assert!(value.is_running());
+    assert_eq!(*value.get(), 0.0);
+    assert_eq!(*value.target(), 10.0);
+
+    assert!(value.advance(Duration::from_millis(50)));
+    assert_near(*value.get(), 5.0);
+
+    // retargeting starts from the current value
+    value.animate_to(0.0, id);
+    assert!(value.advance(Duration::from_millis(50)));
+    assert_near(*value.get(), 2.5);
+    assert!(!value.advance(Duration::from_millis(60)));
+    assert_eq!(*value.get(), 0.0);
+
+    value.set(3.0);
+    assert!(!value.is_running());
+    assert_eq!(*value.get(), 3.0);
+    timer::take_expired(Instant::now());
+}
+
+#[test]
+fn animated_widget_requests_frames() {
+    let fader = Fader {
+        id: WidgetId::new(),
+        bounds: Rect::new([0, 0], [50, 50]),
+        color: Animated::new(Color::BLACK, Duration::from_millis(100), Easing::EASE),
+        frames: 0,
+    };
+    let mut window = Window::new(fader);
+    window.set_size([50, 50]);
+    let mut driver = EventDriver::new(window);
+
+    assert!(driver.move_to([10, 10]));
+    assert!(timer::animation_pending());
+
+    let start = Instant::now();
+    assert!(driver.process_timers(start + Duration::from_millis(40)));
+    assert!(driver.window().child.color.is_running());
+    assert!(timer::animation_pending());
+
+    // the last frame settles the value and stops requesting frames
+    assert!(driver.process_timers(start + Duration::from_secs(1)));
+    assert_eq!(*driver.window().child.color.get(), Color::WHITE);
+    assert!(!timer::animation_pending());
+    assert!(!driver.process_timers(start + Duration::from_secs(2)));
+    assert_eq!(driver.window().child.frames, 2);
+}
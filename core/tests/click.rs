@@ -0,0 +1,127 @@
+mod common;
+use common::Recorder;
+use rtk::event::{ClickSettings, MouseButton};
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+use std::time::Duration;
+
+fn driver() -> EventDriver<Window<Recorder>> {
+    let recorder = Recorder::new(Rect::new([10, 10], [50, 50]), |event| {
+        matches!(event, Event::Click(_) | Event::DoubleClick(_) | Event::LongPress(_))
+    })
+    .with_result(|event| match event {
+        Event::MouseButton(_, _) | Event::Click(_) | Event::DoubleClick(_) | Event::LongPress(_) => EventResult::Consumed,
+        _ => EventResult::Pass,
+    });
+    let mut window = Window::new(recorder);
+    window.set_size([100, 100]);
+    window.dispatcher_mut().set_click_settings(ClickSettings {
+        long_press_time: Duration::from_millis(50),
+        double_click_interval: Duration::from_secs(10),
+        ..Default::default()
+    });
+    EventDriver::new(window)
+}
+
+fn take_events(driver: &mut EventDriver<Window<Recorder>>) -> Vec<Event> {
+    driver.window_mut().child.take_events()
+}
+
+#[test]
+fn click_single_double() {
+    let mut driver = driver();
+    assert!(driver.click_at([20, 20], MouseButton::Left));
+    assert_eq!(take_events(&mut driver), vec![Event::Click(MouseButton::Left)]);
+
+    driver.click_at([21, 20], MouseButton::Left);
+    assert_eq!(
+        take_events(&mut driver),
+        vec![Event::Click(MouseButton::Left), Event::DoubleClick(MouseButton::Left)]
+    );
+
+    // a third click starts over
+    driver.click_at([21, 20], MouseButton::Left);
+    assert_eq!(take_events(&mut driver), vec![Event::Click(MouseButton::Left)]);
+
+    // different button isn't a double click
+    driver.click_at([21, 20], MouseButton::Right);
+    assert_eq!(take_events(&mut driver), vec![Event::Click(MouseButton::Right)]);
+
+    // too far from the previous click
+    driver.click_at([40, 40], MouseButton::Right);
+    assert_eq!(take_events(&mut driver), vec![Event::Click(MouseButton::Right)]);
+}
+
+#[test]
+fn click_cancelled_by_motion() {
+    let mut driver = driver();
+    driver.drag([20, 20], [50, 20]);
+    assert_eq!(take_events(&mut driver), vec![]);
+
+    // moving away and back also cancels
+    driver.move_to([20, 20]);
+    driver.press(MouseButton::Left);
+    driver.move_to([40, 20]);
+    driver.move_to([20, 20]);
+    driver.release(MouseButton::Left);
+    assert_eq!(take_events(&mut driver), vec![]);
+
+    // small motion is allowed
+    driver.press(MouseButton::Left);
+    driver.move_to([22, 21]);
+    driver.release(MouseButton::Left);
+    assert_eq!(take_events(&mut driver), vec![Event::Click(MouseButton::Left)]);
+}
+
+#[test]
+fn click_long_press() {
+    let mut driver = driver();
+    driver.move_to([20, 20]);
+    driver.press(MouseButton::Left);
+    driver.advance(Duration::from_millis(40));
+    assert_eq!(take_events(&mut driver), vec![]);
+
+    // sent while the button is still down, the release isn't a click
+    assert!(driver.advance(Duration::from_millis(10)));
+    assert_eq!(take_events(&mut driver), vec![Event::LongPress(MouseButton::Left)]);
+    driver.release(MouseButton::Left);
+    assert_eq!(take_events(&mut driver), vec![]);
+
+    // a release before the long press time is a click, and the timer is cancelled
+    driver.press(MouseButton::Left);
+    driver.advance(Duration::from_millis(40));
+    driver.release(MouseButton::Left);
+    driver.advance(Duration::from_millis(100));
+    assert_eq!(take_events(&mut driver), vec![Event::Click(MouseButton::Left)]);
+}
+
+#[test]
+fn click_double_interval() {
+    let mut driver = driver();
+    driver.window_mut().dispatcher_mut().set_click_settings(ClickSettings {
+        double_click_interval: Duration::from_millis(300),
+        ..Default::default()
+    });
+    driver.click_at([20, 20], MouseButton::Left);
+    driver.advance(Duration::from_millis(400));
+    driver.click_at([20, 20], MouseButton::Left);
+    assert_eq!(
+        take_events(&mut driver),
+        vec![Event::Click(MouseButton::Left), Event::Click(MouseButton::Left)]
+    );
+
+    driver.advance(Duration::from_millis(200));
+    driver.click_at([20, 20], MouseButton::Left);
+    assert_eq!(
+        take_events(&mut driver),
+        vec![Event::Click(MouseButton::Left), Event::DoubleClick(MouseButton::Left)]
+    );
+}
+
+#[test]
+fn click_outside() {
+    let mut driver = driver();
+    driver.click_at([5, 5], MouseButton::Left);
+    assert_eq!(take_events(&mut driver), vec![]);
+}
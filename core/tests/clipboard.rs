@@ -0,0 +1,53 @@
+use rtk::backend::{Clipboard, ClipboardError};
+use rtk::clipboard::{ClipboardContent, ClipboardRequest, MemoryClipboard};
+use rtk::image::{Image, PixelFormat};
+use rtk::testing::{SoftwareBackend, TestBackend};
+
+#[test]
+fn memory_clipboard() {
+    let mut mem = MemoryClipboard::default();
+    assert_eq!(mem.get_text(), None);
+    mem.set_text("hello").unwrap();
+    assert_eq!(mem.get_text().as_deref(), Some("hello"));
+    assert_eq!(mem.get_image(), None);
+
+    let image = Image::new(vec![255u8; 4], [1, 1], PixelFormat::Rgba);
+    mem.set_image(&image).unwrap();
+    assert_eq!(mem.get_text(), None);
+    assert_eq!(mem.content, Some(ClipboardContent::Image(image)));
+}
+
+struct TextOnly(String);
+
+impl Clipboard for TextOnly {
+    fn get_text(&mut self) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.0 = text.into();
+        Ok(())
+    }
+}
+
+#[test]
+fn clipboard_text_only() {
+    let mut clipboard = TextOnly("text".into());
+    let image = Image::new_empty([1, 1], PixelFormat::Rgba);
+    assert_eq!(clipboard.set_image(&image), Err(ClipboardError::FormatNotSupported));
+    assert_eq!(clipboard.get_image(), None);
+}
+
+#[test]
+fn clipboard_request() {
+    let mut backend = TestBackend::default();
+    assert_eq!(ClipboardRequest::Paste.apply(&mut backend), None);
+    assert_eq!(ClipboardRequest::Copy("hello".into()).apply(&mut backend), None);
+    assert_eq!(backend.clipboard.get_text().as_deref(), Some("hello"));
+    assert_eq!(ClipboardRequest::Paste.apply(&mut backend).as_deref(), Some("hello"));
+
+    // backends without a clipboard ignore the requests
+    let mut backend = SoftwareBackend::new([1, 1]);
+    assert_eq!(ClipboardRequest::Copy("hello".into()).apply(&mut backend), None);
+    assert_eq!(ClipboardRequest::Paste.apply(&mut backend), None);
+}
@@ -0,0 +1,172 @@
+//! Test widgets shared by the event dispatch tests.
+#![allow(dead_code)]
+use rtk::geometry::Point;
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+use rtk_derive::{Bounds, ObjectId, Visitable};
+
+/// Widget that records the events selected by a filter, with their position relative to the widget.
+///
+/// Recorded events are consumed and the rest are passed, unless the result is set with `with_result`.
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+pub struct Recorder {
+    pub id: WidgetId,
+    pub bounds: Rect,
+    pub events: Vec<(Event, Point<f64>)>,
+    record: fn(&Event) -> bool,
+    result: Option<fn(&Event) -> EventResult>,
+    tab_index: Option<u32>,
+    ime_area: Option<Rect>,
+}
+
+impl Recorder {
+    pub fn new(bounds: impl Into<Rect>, record: fn(&Event) -> bool) -> Self {
+        Recorder {
+            id: WidgetId::new(),
+            bounds: bounds.into(),
+            events: vec![],
+            record,
+            result: None,
+            tab_index: None,
+            ime_area: None,
+        }
+    }
+
+    /// Sets the result returned for every event.
+    pub fn with_result(self, result: fn(&Event) -> EventResult) -> Self {
+        Recorder {
+            result: Some(result),
+            ..self
+        }
+    }
+
+    /// Makes the widget accept focus.
+    pub fn with_focus(self, tab_index: u32) -> Self {
+        Recorder {
+            tab_index: Some(tab_index),
+            ..self
+        }
+    }
+
+    /// Sets the input method cursor area.
+    pub fn with_ime_area(self, area: impl Into<Rect>) -> Self {
+        Recorder {
+            ime_area: Some(area.into()),
+            ..self
+        }
+    }
+
+    /// Gets the recorded events without their positions.
+    pub fn events(&self) -> Vec<Event> {
+        self.events.iter().map(|(event, _)| event.clone()).collect()
+    }
+
+    /// Removes the recorded events and returns them without their positions.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events).into_iter().map(|(event, _)| event).collect()
+    }
+}
+
+impl Widget for Recorder {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        let recorded = (self.record)(event);
+        if recorded {
+            self.events.push((event.clone(), ctx.local_pos));
+        }
+        match self.result {
+            Some(result) => result(event),
+            None if recorded => EventResult::Consumed,
+            None => EventResult::Pass,
+        }
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+
+    fn accepts_focus(&self) -> bool {
+        self.tab_index.is_some()
+    }
+
+    fn tab_index(&self) -> u32 {
+        self.tab_index.unwrap_or(0)
+    }
+
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        self.ime_area
+    }
+}
+
+/// Container of `Recorder` widgets that also records the events selected by a filter.
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+pub struct Parent {
+    pub id: WidgetId,
+    pub bounds: Rect,
+    #[visit_iter]
+    pub childs: Vec<Recorder>,
+    pub events: Vec<Event>,
+    record: fn(&Event) -> bool,
+    viewport_origin: Position,
+}
+
+impl Parent {
+    pub fn new(bounds: impl Into<Rect>, childs: Vec<Recorder>) -> Self {
+        Parent {
+            id: WidgetId::new(),
+            bounds: bounds.into(),
+            childs,
+            events: vec![],
+            record: |_| false,
+            viewport_origin: Default::default(),
+        }
+    }
+
+    /// Sets the events that this widget records and consumes.
+    pub fn with_record(self, record: fn(&Event) -> bool) -> Self {
+        Parent { record, ..self }
+    }
+
+    /// Scrolls the content by setting the origin of the viewport.
+    pub fn with_viewport_origin(self, origin: impl Into<Position>) -> Self {
+        Parent {
+            viewport_origin: origin.into(),
+            ..self
+        }
+    }
+}
+
+impl Widget for Parent {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, event: &Event, _ctx: EventContext) -> EventResult {
+        if (self.record)(event) {
+            self.events.push(event.clone());
+            EventResult::Consumed
+        } else {
+            EventResult::Pass
+        }
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+
+    fn viewport_origin(&self) -> Position {
+        self.viewport_origin
+    }
+}
+
+/// Creates a driver for a 100x50 window with two 20x20 recorders, at (10, 10) and (50, 10).
+pub fn pair_driver(record: fn(&Event) -> bool) -> EventDriver<Window<Parent>> {
+    let parent = Parent::new(
+        Rect::new_at_origin([100, 50]),
+        vec![
+            Recorder::new(Rect::new([10, 10], [20, 20]), record),
+            Recorder::new(Rect::new([50, 10], [20, 20]), record),
+        ],
+    );
+    EventDriver::new(Window::new(parent))
+}
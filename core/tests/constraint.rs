@@ -0,0 +1,62 @@
+#![cfg(feature = "constraint")]
+use rtk::geometry::Rect;
+use rtk::layout::constraint::{ConstraintError, ConstraintLayout, EQ, GE, REQUIRED, STRONG, WEAK};
+
+fn rects(sizes: &[[u32; 2]]) -> Vec<Rect> {
+    sizes.iter().map(|&size| Rect::new_at_origin(size)).collect()
+}
+
+#[test]
+fn constraint_form() {
+    let mut layout = ConstraintLayout::new();
+    let parent = layout.parent();
+    let label = layout.add_item();
+    let field = layout.add_item();
+    layout
+        .add_constraints(vec![
+            label.left | EQ(REQUIRED) | (parent.left + 10.0),
+            label.top | EQ(REQUIRED) | (parent.top + 10.0),
+            label.width | EQ(STRONG) | 60.0,
+            label.height | EQ(REQUIRED) | 20.0,
+            field.left | EQ(REQUIRED) | (label.right() + 8.0),
+            field.top | EQ(REQUIRED) | label.top,
+            field.right() | EQ(REQUIRED) | (parent.right() - 10.0),
+            field.width | GE(REQUIRED) | 100.0,
+            field.height | EQ(REQUIRED) | label.height,
+        ])
+        .unwrap();
+
+    let mut items = rects(&[[0, 0]; 2]);
+    let anchors = [label, field];
+    layout.apply(items.iter_mut().zip(anchors.iter().copied()), Rect::new([10, 10], [300, 100]));
+    assert_eq!(items, vec![Rect::new([20, 20], [60, 20]), Rect::new([88, 20], [212, 20])]);
+
+    // the field can't shrink below it's minimum, the weaker label width gives way
+    layout.apply(items.iter_mut().zip(anchors.iter().copied()), Rect::new_at_origin([150, 100]));
+    assert_eq!(items, vec![Rect::new([10, 10], [22, 20]), Rect::new([40, 10], [100, 20])]);
+}
+
+#[test]
+fn constraint_errors() {
+    let mut layout = ConstraintLayout::default();
+    let parent = layout.parent();
+    let item = layout.add_item();
+    let center = item.center_x() | EQ(REQUIRED) | parent.center_x();
+    assert_eq!(layout.add_constraint(center.clone()), Ok(()));
+    assert_eq!(layout.add_constraint(center.clone()), Err(ConstraintError::Duplicate));
+    // items can't have a negative size
+    assert_eq!(
+        layout.add_constraint(item.width | EQ(REQUIRED) | -5.0),
+        Err(ConstraintError::Unsatisfiable)
+    );
+    assert_eq!(layout.add_constraint(item.width | EQ(WEAK) | 40.0), Ok(()));
+    assert_eq!(layout.bounds(&item), Rect::new([-20, 0], [40, 0]));
+
+    let mut rect = Rect::default();
+    layout.apply(vec![(&mut rect, item)], Rect::new_at_origin([100, 100]));
+    assert_eq!(rect, Rect::new([30, 0], [40, 0]));
+
+    assert_eq!(layout.remove_constraint(&center), Ok(()));
+    assert!(!layout.has_constraint(&center));
+    assert_eq!(layout.remove_constraint(&center), Err(ConstraintError::Unknown));
+}
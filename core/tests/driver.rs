@@ -0,0 +1,168 @@
+use rtk::event::{Axis, ButtonState, Key, KeyModState, MouseButton, ScrollDelta, ScrollPhase};
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+use rtk::visitor::{Visitable, Visitor};
+
+/// Widget that records every event it receives.
+#[derive(Debug, Clone)]
+struct Recorder {
+    id: WidgetId,
+    bounds: Rect,
+    events: Vec<Event>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        Recorder {
+            id: WidgetId::new(),
+            bounds: Rect::new([10, 10], [50, 50]),
+            events: vec![],
+        }
+    }
+}
+
+impl ObjectId for Recorder {
+    fn get_id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl Bounds for Recorder {
+    fn get_position(&self) -> Position {
+        self.bounds.pos
+    }
+
+    fn get_size(&self) -> Size {
+        self.bounds.size
+    }
+}
+
+impl Visitable for Recorder {
+    fn accept<V: Visitor>(&mut self, visitor: V, prev_ctx: &V::Context) -> V {
+        if let Some(ctx) = visitor.new_context(self, prev_ctx) {
+            visitor.visit_before(self, &ctx).visit_after(self, &ctx)
+        } else {
+            visitor
+        }
+    }
+}
+
+impl Widget for Recorder {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, event: &Event, _ctx: EventContext) -> EventResult {
+        self.events.push(event.clone());
+        EventResult::Consumed
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+fn driver() -> EventDriver<Window<Recorder>> {
+    let mut window = Window::new(Recorder::new());
+    window.set_size([100, 100]);
+    EventDriver::new(window)
+}
+
+fn take_events(driver: &mut EventDriver<Window<Recorder>>) -> Vec<Event> {
+    std::mem::take(&mut driver.window_mut().child.events)
+}
+
+fn key(state: ButtonState, key: Key) -> Event {
+    Event::Keyboard { state, key, scancode: 0 }
+}
+
+#[test]
+fn driver_click() {
+    let mut driver = driver();
+    assert!(driver.click_at([20, 20], MouseButton::Left));
+    assert_eq!(
+        take_events(&mut driver),
+        vec![
+            Event::PointerInside(true),
+            Event::MouseMoved(Axis::Position([20.0, 20.0].into())),
+            Event::MouseButton(ButtonState::Pressed, MouseButton::Left),
+            Event::MouseButton(ButtonState::Released, MouseButton::Left),
+            Event::Click(MouseButton::Left),
+        ]
+    );
+
+    // outside the widget, only the leave notification is received
+    driver.click_at([5, 5], MouseButton::Right);
+    assert_eq!(take_events(&mut driver), vec![Event::PointerInside(false)]);
+    assert_eq!(driver.pointer_pos(), Some([5.0, 5.0].into()));
+
+    driver.leave();
+    assert_eq!(driver.pointer_pos(), None);
+}
+
+#[test]
+fn driver_drag() {
+    let mut driver = driver();
+    driver.drag([20, 20], [40, 30]);
+    // the synthesized pan gesture is tested elsewhere
+    let events: Vec<_> = take_events(&mut driver)
+        .into_iter()
+        .filter(|event| !matches!(event, Event::Gesture(_)))
+        .collect();
+    assert_eq!(events[2], Event::MouseButton(ButtonState::Pressed, MouseButton::Left));
+    assert_eq!(events[events.len() - 2], Event::MouseMoved(Axis::Position([40.0, 30.0].into())));
+    assert_eq!(
+        events[events.len() - 1],
+        Event::MouseButton(ButtonState::Released, MouseButton::Left)
+    );
+}
+
+#[test]
+fn driver_keyboard() {
+    let mut driver = driver();
+    assert!(driver.type_text("aB"));
+    let shift = KeyModState {
+        shift: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        take_events(&mut driver),
+        vec![
+            key(ButtonState::Pressed, Key::Letter('a')),
+            Event::Character('a'),
+            key(ButtonState::Released, Key::Letter('a')),
+            Event::ModifiersChanged(shift),
+            key(ButtonState::Pressed, Key::Letter('b')),
+            Event::Character('B'),
+            key(ButtonState::Released, Key::Letter('b')),
+            Event::ModifiersChanged(Default::default()),
+        ]
+    );
+
+    let ctrl = KeyModState {
+        ctrl: true,
+        ..Default::default()
+    };
+    driver.press_key(Key::Letter('c'), ctrl);
+    assert_eq!(
+        take_events(&mut driver),
+        vec![
+            Event::ModifiersChanged(ctrl),
+            key(ButtonState::Pressed, Key::Letter('c')),
+            key(ButtonState::Released, Key::Letter('c')),
+            Event::ModifiersChanged(Default::default()),
+        ]
+    );
+    assert_eq!(driver.mod_state(), Default::default());
+}
+
+#[test]
+fn driver_scroll() {
+    let mut driver = driver();
+    driver.move_to([30, 30]);
+    take_events(&mut driver);
+    assert!(driver.scroll(0.0, -1.0));
+    assert_eq!(
+        take_events(&mut driver),
+        vec![Event::MouseMoved(Axis::Scroll(ScrollDelta::Lines(0.0, -1.0), ScrollPhase::Moved))]
+    );
+}
@@ -0,0 +1,141 @@
+mod common;
+use common::{Parent, Recorder};
+use rtk::event::{ButtonState, Key, KeyModState, MouseButton};
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+
+fn child(pos: impl Into<Position>) -> Recorder {
+    Recorder::new(Rect::new(pos, [10, 10]), |event| {
+        matches!(event, Event::Character('x') | Event::FocusGained | Event::FocusLost)
+    })
+    .with_result(|event| match event {
+        Event::MouseButton(ButtonState::Pressed, _) => EventResult::ConsumedFocus,
+        Event::Character('x') | Event::FocusGained | Event::FocusLost => EventResult::Consumed,
+        _ => EventResult::Pass,
+    })
+    .with_focus(0)
+}
+
+fn driver_with(childs: Vec<Recorder>) -> EventDriver<Window<Parent>> {
+    let parent = Parent::new(Rect::new_at_origin([50, 50]), childs)
+        .with_record(|event| matches!(event, Event::Character(_) | Event::Keyboard { key: Key::Letter(_), .. }));
+    EventDriver::new(Window::new(parent))
+}
+
+fn driver() -> EventDriver<Window<Parent>> {
+    driver_with(vec![child([0, 0]), child([20, 0])])
+}
+
+#[test]
+fn focus_click() {
+    let mut driver = driver();
+    let ids: Vec<_> = driver.window().child.childs.iter().map(|c| c.id).collect();
+    assert_eq!(driver.window().focused(), None);
+
+    // without focus, keyboard events are broadcast until consumed
+    driver.type_text("x");
+    assert_eq!(driver.window().child.childs[0].events(), vec![Event::Character('x')]);
+    driver.window_mut().child.childs[0].events.clear();
+    driver.window_mut().child.events.clear();
+
+    assert!(driver.click_at([25, 5], MouseButton::Left));
+    assert_eq!(driver.window().focused(), Some(ids[1]));
+    assert_eq!(driver.window().child.childs[1].events(), vec![Event::FocusGained]);
+
+    driver.type_text("xy");
+    let window = &mut driver.window_mut();
+    assert_eq!(window.child.childs[0].events(), vec![]);
+    assert_eq!(window.child.childs[1].events(), vec![Event::FocusGained, Event::Character('x')]);
+    // events not consumed by the focused widget bubble up to the parent
+    assert_eq!(
+        window.child.events,
+        vec![
+            Event::Keyboard {
+                state: ButtonState::Pressed,
+                key: Key::Letter('x'),
+                scancode: 0
+            },
+            Event::Keyboard {
+                state: ButtonState::Released,
+                key: Key::Letter('x'),
+                scancode: 0
+            },
+            Event::Keyboard {
+                state: ButtonState::Pressed,
+                key: Key::Letter('y'),
+                scancode: 0
+            },
+            Event::Character('y'),
+            Event::Keyboard {
+                state: ButtonState::Released,
+                key: Key::Letter('y'),
+                scancode: 0
+            },
+        ]
+    );
+
+    driver.click_at([5, 5], MouseButton::Left);
+    assert_eq!(driver.window().focused(), Some(ids[0]));
+    assert_eq!(driver.window().child.childs[0].events(), vec![Event::FocusGained]);
+    assert_eq!(driver.window().child.childs[1].events().last(), Some(&Event::FocusLost));
+}
+
+#[test]
+fn focus_set() {
+    let mut driver = driver();
+    let id = driver.window().child.childs[0].id;
+
+    assert!(driver.window_mut().set_focus(Some(id)));
+    assert!(!driver.window_mut().set_focus(Some(id)));
+    assert!(driver.window_mut().set_focus(None));
+    assert_eq!(driver.window().focused(), None);
+    assert_eq!(driver.window().child.childs[0].events(), vec![Event::FocusGained, Event::FocusLost]);
+
+    // focus is cleared if the widget is removed, and the events are broadcast instead
+    driver.window_mut().set_focus(Some(id));
+    driver.window_mut().child.childs.remove(0);
+    assert!(driver.push_event(Event::Character('a')));
+    assert_eq!(driver.window().focused(), None);
+    assert_eq!(driver.window().child.events, vec![Event::Character('a')]);
+}
+
+#[test]
+fn focus_tab() {
+    let mut driver = driver_with(vec![
+        child([0, 0]),
+        child([10, 0]).with_focus(2),
+        child([20, 0]),
+        child([30, 0]).with_focus(1),
+    ]);
+    let ids: Vec<_> = driver.window().child.childs.iter().map(|c| c.id).collect();
+    let order = [ids[3], ids[1], ids[0], ids[2], ids[3]];
+
+    for &id in &order {
+        assert!(driver.type_text("\t"));
+        assert_eq!(driver.window().focused(), Some(id));
+    }
+    // the tab character isn't delivered to the focused widget
+    assert_eq!(driver.window().child.events, vec![]);
+
+    let shift = KeyModState {
+        shift: true,
+        ..Default::default()
+    };
+    for &id in order.iter().rev().skip(1) {
+        assert!(driver.press_key(Key::Tab, shift));
+        assert_eq!(driver.window().focused(), Some(id));
+    }
+
+    // reverse from no focus starts at the end
+    driver.window_mut().set_focus(None);
+    driver.press_key(Key::Tab, shift);
+    assert_eq!(driver.window().focused(), Some(ids[2]));
+}
+
+#[test]
+fn focus_tab_empty() {
+    let mut driver = driver_with(vec![]);
+    assert!(!driver.press_key(Key::Tab, Default::default()));
+    assert_eq!(driver.window().focused(), None);
+}
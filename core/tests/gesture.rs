@@ -0,0 +1,206 @@
+mod common;
+use common::{Parent, Recorder};
+use rtk::event::{Axis, Gesture, GesturePhase, GestureSettings, MouseButton, SwipeDirection, TouchPhase};
+use rtk::geometry::Point;
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+use std::time::Duration;
+
+fn new_driver(swipe_velocity: f64) -> EventDriver<Window<Parent>> {
+    let canvas = Recorder::new(Rect::new([10, 10], [100, 50]), |event| matches!(event, Event::Gesture(_)));
+    let mut driver = EventDriver::new(Window::new(Parent::new(Rect::new_at_origin([200, 100]), vec![canvas])));
+    driver.window_mut().dispatcher_mut().set_gesture_settings(GestureSettings {
+        pan_threshold: 8.0,
+        swipe_velocity,
+    });
+    driver
+}
+
+fn canvas(driver: &EventDriver<Window<Parent>>) -> &Recorder {
+    &driver.window().child.childs[0]
+}
+
+fn gestures(driver: &EventDriver<Window<Parent>>) -> Vec<Gesture> {
+    canvas(driver)
+        .events
+        .iter()
+        .filter_map(|(event, _)| match *event {
+            Event::Gesture(gesture) => Some(gesture),
+            _ => None,
+        })
+        .collect()
+}
+
+fn pan_deltas(driver: &EventDriver<Window<Parent>>) -> Vec<(GesturePhase, Point<f64>)> {
+    gestures(driver)
+        .into_iter()
+        .filter_map(|gesture| match gesture {
+            Gesture::Pan { phase, delta, .. } => Some((phase, delta)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn swipe_direction() {
+    assert_eq!(SwipeDirection::from_vector([-5.0, 2.0].into()), SwipeDirection::Left);
+    assert_eq!(SwipeDirection::from_vector([5.0, -2.0].into()), SwipeDirection::Right);
+    assert_eq!(SwipeDirection::from_vector([1.0, -3.0].into()), SwipeDirection::Up);
+    assert_eq!(SwipeDirection::from_vector([-1.0, 3.0].into()), SwipeDirection::Down);
+}
+
+#[test]
+fn gesture_mouse_pan() {
+    let mut driver = new_driver(f64::INFINITY);
+    let canvas_id = canvas(&driver).id;
+
+    driver.move_to([20, 20]);
+    driver.press(MouseButton::Left);
+    // below the threshold
+    driver.move_to([24, 20]);
+    assert_eq!(gestures(&driver), vec![]);
+    driver.move_to([30, 20]);
+    assert_eq!(driver.window().dispatcher().gesture_target(), Some(canvas_id));
+    // the pan continues outside the widget
+    driver.advance(Duration::from_millis(16));
+    driver.move_to([150, 80]);
+    driver.release(MouseButton::Left);
+    assert_eq!(driver.window().dispatcher().gesture_target(), None);
+
+    assert_eq!(
+        pan_deltas(&driver),
+        vec![
+            (GesturePhase::Started, [10.0, 0.0].into()),
+            (GesturePhase::Moved, [120.0, 60.0].into()),
+            (GesturePhase::Ended, [0.0, 0.0].into()),
+        ]
+    );
+    // the position is relative to the widget
+    assert_eq!(canvas(&driver).events[1].1, Point::new(140.0, 70.0));
+    match gestures(&driver)[1] {
+        Gesture::Pan { velocity, .. } => assert!(velocity.x > 0.0 && velocity.y > 0.0),
+        other => panic!("unexpected gesture {:?}", other),
+    }
+
+    // moving without the button held doesn't pan
+    driver.move_to([50, 30]);
+    driver.move_to([90, 30]);
+    assert_eq!(gestures(&driver).len(), 3);
+}
+
+#[test]
+fn gesture_pan_pen_state() {
+    let mut driver = new_driver(f64::INFINITY);
+    driver.move_to([20, 20]);
+    driver.push_event(Event::MouseMoved(Axis::Pressure(0.5)));
+    driver.push_event(Event::MouseMoved(Axis::Tilt(0.25, -0.5)));
+    driver.press(MouseButton::Left);
+    driver.move_to([40, 20]);
+    match gestures(&driver)[0] {
+        Gesture::Pan { pressure, tilt, .. } => {
+            assert_eq!(pressure, Some(0.5));
+            assert_eq!(tilt, Some(Point::new(0.25, -0.5)));
+        }
+        other => panic!("unexpected gesture {:?}", other),
+    }
+}
+
+#[test]
+fn gesture_swipe() {
+    let mut driver = new_driver(1.0);
+    driver.touch_drag(1, [100, 30], [20, 35]);
+    let received = gestures(&driver);
+    assert_eq!(received.len(), 6);
+    assert_eq!(received[4].phase(), GesturePhase::Ended);
+    assert_eq!(received[5], Gesture::Swipe(SwipeDirection::Left));
+
+    // the contact stopped before being lifted
+    let mut driver = new_driver(1.0);
+    driver.touch(1, TouchPhase::Started, [20, 30]);
+    driver.touch(1, TouchPhase::Moved, [40, 30]);
+    driver.touch(1, TouchPhase::Moved, [60, 30]);
+    driver.advance(Duration::from_millis(16));
+    driver.touch(1, TouchPhase::Moved, [80, 30]);
+    driver.advance(Duration::from_millis(150));
+    driver.touch(1, TouchPhase::Ended, [80, 30]);
+    let received = gestures(&driver);
+    assert_eq!(received.len(), 4);
+    assert!(matches!(received[2], Gesture::Pan { velocity, .. } if velocity.x > 0.0));
+    assert!(matches!(
+        received[3],
+        Gesture::Pan {
+            phase: GesturePhase::Ended,
+            velocity: Point { x, y },
+            ..
+        } if x == 0.0 && y == 0.0
+    ));
+
+    // cancelled contacts don't swipe
+    let mut driver = new_driver(1.0);
+    driver.touch(1, TouchPhase::Started, [20, 30]);
+    driver.touch(1, TouchPhase::Moved, [60, 30]);
+    driver.touch(1, TouchPhase::Cancelled, [60, 30]);
+    assert_eq!(gestures(&driver).len(), 2);
+}
+
+#[test]
+fn gesture_pinch() {
+    let mut driver = new_driver(f64::INFINITY);
+    driver.touch(1, TouchPhase::Started, [30, 30]);
+    driver.touch(2, TouchPhase::Started, [50, 30]);
+    driver.touch(2, TouchPhase::Moved, [70, 30]);
+    driver.touch(1, TouchPhase::Moved, [20, 30]);
+    driver.touch(1, TouchPhase::Ended, [20, 30]);
+    // the remaining contact doesn't start a new gesture
+    driver.touch(2, TouchPhase::Moved, [120, 30]);
+    driver.touch(2, TouchPhase::Ended, [120, 30]);
+
+    let pinch = |phase, scale, x| Gesture::Pinch {
+        phase,
+        scale,
+        center: Point::new(x, 30.0),
+    };
+    assert_eq!(
+        gestures(&driver),
+        vec![
+            pinch(GesturePhase::Started, 1.0, 40.0),
+            pinch(GesturePhase::Moved, 2.0, 50.0),
+            pinch(GesturePhase::Moved, 2.5, 45.0),
+            pinch(GesturePhase::Ended, 2.5, 45.0),
+        ]
+    );
+    assert_eq!(canvas(&driver).events[0].1, Point::new(30.0, 20.0));
+}
+
+#[test]
+fn gesture_pan_into_pinch() {
+    let mut driver = new_driver(f64::INFINITY);
+    driver.touch(1, TouchPhase::Started, [30, 30]);
+    driver.touch(1, TouchPhase::Moved, [40, 30]);
+    driver.touch(2, TouchPhase::Started, [80, 30]);
+    let phases: Vec<_> = gestures(&driver)
+        .into_iter()
+        .map(|gesture| match gesture {
+            Gesture::Pan { phase, .. } => ("pan", phase),
+            Gesture::Pinch { phase, .. } => ("pinch", phase),
+            Gesture::Swipe(_) => ("swipe", gesture.phase()),
+        })
+        .collect();
+    assert_eq!(
+        phases,
+        vec![
+            ("pan", GesturePhase::Started),
+            ("pan", GesturePhase::Ended),
+            ("pinch", GesturePhase::Started)
+        ]
+    );
+}
+
+#[test]
+fn gesture_outside_widgets() {
+    let mut driver = new_driver(f64::INFINITY);
+    assert!(!driver.touch_drag(1, [150, 80], [20, 20]));
+    assert_eq!(driver.window().dispatcher().gesture_target(), None);
+    assert_eq!(gestures(&driver), vec![]);
+}
@@ -0,0 +1,166 @@
+mod common;
+use common::{pair_driver, Parent, Recorder};
+use rtk::event::{Axis, ButtonState, MouseButton, ScrollDelta, ScrollPhase};
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+
+fn driver() -> EventDriver<Window<Parent>> {
+    pair_driver(|event| matches!(event, Event::MouseButton(_, _) | Event::MouseMoved(_)))
+}
+
+fn moved(x: f64, y: f64) -> Event {
+    Event::MouseMoved(Axis::Position([x, y].into()))
+}
+
+#[test]
+fn grab_drag() {
+    let mut driver = driver();
+    let slider_id = driver.window().child.childs[0].id;
+
+    driver.move_to([15, 15]);
+    driver.press(MouseButton::Left);
+    assert_eq!(driver.window().grabbed(), Some(slider_id));
+
+    driver.move_to([60, 20]);
+    driver.move_to([80, 45]);
+    driver.release(MouseButton::Left);
+    assert_eq!(driver.window().grabbed(), None);
+
+    let window = driver.window_mut();
+    assert_eq!(window.child.childs[1].events, vec![]);
+    // positions are still relative to the grabbing widget
+    assert_eq!(
+        window.child.childs[0].events,
+        vec![
+            (moved(15.0, 15.0), [5.0, 5.0].into()),
+            (Event::MouseButton(ButtonState::Pressed, MouseButton::Left), [5.0, 5.0].into()),
+            (moved(60.0, 20.0), [50.0, 10.0].into()),
+            (moved(80.0, 45.0), [70.0, 35.0].into()),
+            (Event::MouseButton(ButtonState::Released, MouseButton::Left), [70.0, 35.0].into()),
+        ]
+    );
+    window.child.childs[0].events.clear();
+
+    // after release, events go to the widget under the pointer
+    driver.move_to([55, 15]);
+    let window = driver.window();
+    assert_eq!(window.child.childs[0].events, vec![]);
+    assert_eq!(window.child.childs[1].events, vec![(moved(55.0, 15.0), [5.0, 5.0].into())]);
+}
+
+#[test]
+fn grab_multiple_buttons() {
+    let mut driver = driver();
+    let slider_id = driver.window().child.childs[0].id;
+
+    driver.move_to([15, 15]);
+    driver.press(MouseButton::Left);
+    driver.move_to([55, 15]);
+    driver.press(MouseButton::Right);
+    driver.release(MouseButton::Left);
+    assert_eq!(driver.window().grabbed(), Some(slider_id));
+    driver.release(MouseButton::Right);
+    assert_eq!(driver.window().grabbed(), None);
+    assert_eq!(driver.window().child.childs[1].events, vec![]);
+
+    // explicit release
+    driver.press(MouseButton::Left);
+    let other_id = driver.window().child.childs[1].id;
+    assert_eq!(driver.window().grabbed(), Some(other_id));
+    driver.window_mut().release_grab();
+    driver.move_to([20, 20]);
+    assert_eq!(
+        driver.window().child.childs[0].events.last(),
+        Some(&(moved(20.0, 20.0), [10.0, 10.0].into()))
+    );
+}
+
+fn is_pointer(event: &Event) -> bool {
+    matches!(event, Event::MouseButton(_, _) | Event::MouseMoved(_))
+}
+
+/// Driver where the first widget handles pointer events with `result`.
+fn driver_with(result: fn(&Event) -> EventResult) -> EventDriver<Window<Parent>> {
+    let parent = Parent::new(
+        Rect::new_at_origin([100, 50]),
+        vec![
+            Recorder::new(Rect::new([10, 10], [20, 20]), is_pointer).with_result(result),
+            Recorder::new(Rect::new([50, 10], [20, 20]), is_pointer),
+        ],
+    );
+    EventDriver::new(Window::new(parent))
+}
+
+#[test]
+fn grab_widget_release() {
+    // the widget releases the grab when dragged past x = 40
+    let mut driver = driver_with(|event| match event {
+        Event::MouseMoved(Axis::Position(pos)) if pos.x >= 40.0 => EventResult::ConsumedReleaseGrab,
+        _ if is_pointer(event) => EventResult::Consumed,
+        _ => EventResult::Pass,
+    });
+    let slider_id = driver.window().child.childs[0].id;
+
+    driver.move_to([15, 15]);
+    driver.press(MouseButton::Left);
+    driver.move_to([35, 15]);
+    assert_eq!(driver.window().grabbed(), Some(slider_id));
+    driver.move_to([45, 15]);
+    assert_eq!(driver.window().grabbed(), None);
+
+    // the rest of the drag goes to the widget under the pointer
+    driver.move_to([55, 15]);
+    driver.release(MouseButton::Left);
+    let window = driver.window();
+    assert_eq!(window.child.childs[0].events.last(), Some(&(moved(45.0, 15.0), [35.0, 5.0].into())));
+    assert_eq!(
+        window.child.childs[1].events,
+        vec![
+            (moved(55.0, 15.0), [5.0, 5.0].into()),
+            (Event::MouseButton(ButtonState::Released, MouseButton::Left), [5.0, 5.0].into()),
+        ]
+    );
+}
+
+#[test]
+fn grab_widget_request() {
+    // the widget grabs the pointer when hovered, without a button press
+    let mut driver = driver_with(|event| match event {
+        Event::MouseMoved(_) => EventResult::ConsumedGrab,
+        _ if is_pointer(event) => EventResult::Consumed,
+        _ => EventResult::Pass,
+    });
+    let slider_id = driver.window().child.childs[0].id;
+
+    driver.move_to([15, 15]);
+    assert_eq!(driver.window().grabbed(), Some(slider_id));
+    driver.move_to([55, 15]);
+    assert_eq!(driver.window().child.childs[1].events, vec![]);
+
+    // it ends when the buttons are released
+    driver.press(MouseButton::Left);
+    driver.release(MouseButton::Left);
+    assert_eq!(driver.window().grabbed(), None);
+    driver.move_to([60, 15]);
+    assert_eq!(
+        driver.window().child.childs[1].events,
+        vec![(moved(60.0, 15.0), [10.0, 5.0].into())]
+    );
+}
+
+#[test]
+fn grab_scroll_not_captured() {
+    let mut driver = driver();
+    driver.move_to([15, 15]);
+    driver.press(MouseButton::Left);
+    driver.move_to([55, 15]);
+    driver.scroll(0.0, 1.0);
+    assert_eq!(
+        driver.window().child.childs[1].events,
+        vec![(
+            Event::MouseMoved(Axis::Scroll(ScrollDelta::Lines(0.0, 1.0), ScrollPhase::Moved)),
+            [5.0, 5.0].into()
+        )]
+    );
+}
@@ -0,0 +1,46 @@
+mod common;
+use common::{Parent, Recorder};
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::{TopLevel, Window};
+
+fn child(pos: impl Into<Position>) -> Recorder {
+    Recorder::new(Rect::new(pos, [20, 10]), |event| {
+        matches!(event, Event::ImePreedit { .. } | Event::ImeCommit(_))
+    })
+    .with_focus(0)
+    .with_ime_area(Rect::new([4, 1], [1, 8]))
+}
+
+fn driver() -> EventDriver<Window<Parent>> {
+    let parent = Parent::new(Rect::new([5, 5], [50, 50]), vec![child([0, 0]), child([0, 20])]).with_viewport_origin([0, 10]);
+    EventDriver::new(Window::new(parent))
+}
+
+#[test]
+fn ime_events_go_to_focus() {
+    let mut driver = driver();
+    let id = driver.window().child.childs[1].id;
+    driver.window_mut().set_focus(Some(id));
+
+    let preedit = Event::ImePreedit {
+        text: "にほ".into(),
+        cursor: Some((6, 6)),
+    };
+    assert!(driver.push_event(preedit.clone()));
+    assert!(driver.push_event(Event::ImeCommit("日本".into())));
+    let childs = &driver.window().child.childs;
+    assert_eq!(childs[0].events(), vec![]);
+    assert_eq!(childs[1].events(), vec![preedit, Event::ImeCommit("日本".into())]);
+}
+
+#[test]
+fn ime_cursor_area() {
+    let mut driver = driver();
+    assert_eq!(driver.window_mut().ime_cursor_area(), None);
+
+    let id = driver.window().child.childs[1].id;
+    driver.window_mut().set_focus(Some(id));
+    // parent at (5, 5) scrolled down by 10, child at (0, 20)
+    assert_eq!(driver.window_mut().ime_cursor_area(), Some(Rect::new([9, 16], [1, 8])));
+}
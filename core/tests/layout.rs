@@ -0,0 +1,290 @@
+use rtk::geometry::{Alignment, HAlign, Rect, Size, VAlign};
+use rtk::layout::{flex, grid, hbox, vbox, AlignItems, BoxItem, FlexDirection, FlexItem, FlexLayout, GridCell, Justify, Track};
+
+fn rects(sizes: &[[u32; 2]]) -> Vec<Rect> {
+    sizes.iter().map(|&size| Rect::new_at_origin(size)).collect()
+}
+
+#[test]
+fn hbox_stretch() {
+    let mut items = rects(&[[10, 10], [10, 20], [10, 30]]);
+    let specs = [BoxItem::fixed(20), BoxItem::stretch(1), BoxItem::stretch(2)];
+    hbox(
+        items.iter_mut().zip(specs.iter().copied()),
+        Rect::new([5, 5], [130, 40]),
+        5,
+        VAlign::Top,
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([5, 5], [20, 10]),
+            Rect::new([30, 5], [34, 20]),
+            Rect::new([69, 5], [66, 30]),
+        ]
+    );
+}
+
+#[test]
+fn hbox_min_max() {
+    let mut items = rects(&[[10, 10]; 3]);
+    // the capped item leaves the rest of the space to the others
+    let specs = [BoxItem::stretch(1).with_max(10), BoxItem::stretch(1), BoxItem::stretch(1)];
+    hbox(
+        items.iter_mut().zip(specs.iter().copied()),
+        Rect::new_at_origin([100, 10]),
+        0,
+        VAlign::Top,
+    );
+    let widths: Vec<_> = items.iter().map(|r| r.size.w).collect();
+    assert_eq!(widths, vec![10, 45, 45]);
+
+    // not enough space, items shrink towards their minimum
+    let specs = [
+        BoxItem::fixed(40),
+        BoxItem::stretch(1).with_size(60).with_min(20),
+        BoxItem::stretch(1).with_size(40).with_min(30),
+    ];
+    hbox(
+        items.iter_mut().zip(specs.iter().copied()),
+        Rect::new_at_origin([110, 10]),
+        0,
+        VAlign::Top,
+    );
+    let widths: Vec<_> = items.iter().map(|r| r.size.w).collect();
+    assert_eq!(widths, vec![40, 36, 34]);
+
+    // below the minimum the items overflow
+    hbox(
+        items.iter_mut().zip(specs.iter().copied()),
+        Rect::new_at_origin([50, 10]),
+        0,
+        VAlign::Top,
+    );
+    assert_eq!(items[2], Rect::new([60, 0], [30, 10]));
+}
+
+#[test]
+fn hbox_alignment() {
+    let mut items = rects(&[[10, 10], [10, 20]]);
+    let specs = [BoxItem::fixed(10), BoxItem::fixed(20).with_fill()];
+    let align = Alignment {
+        horizontal: HAlign::Right,
+        vertical: VAlign::Center,
+    };
+    hbox(
+        items.iter_mut().zip(specs.iter().copied()),
+        Rect::new_at_origin([100, 40]),
+        10,
+        align,
+    );
+    assert_eq!(items, vec![Rect::new([60, 15], [10, 10]), Rect::new([80, 0], [20, 40])]);
+}
+
+#[test]
+fn vbox_form() {
+    // a title, two fields and a bottom spacer
+    let mut items = rects(&[[40, 16], [200, 20], [200, 20], [0, 0]]);
+    let field = BoxItem::fixed(20).with_fill();
+    let specs = [BoxItem::fixed(16), field, field, BoxItem::stretch(1)];
+    vbox(
+        items.iter_mut().zip(specs.iter().copied()),
+        Rect::new([10, 10], [100, 200]),
+        4,
+        HAlign::Center,
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([40, 10], [40, 16]),
+            Rect::new([10, 30], [100, 20]),
+            Rect::new([10, 54], [100, 20]),
+            Rect::new([60, 78], [0, 132]),
+        ]
+    );
+}
+
+#[test]
+fn grid_form() {
+    // name: [field]
+    // email: [field]
+    //           [button]
+    let mut items = rects(&[[40, 16], [100, 20], [48, 16], [100, 20], [60, 24]]);
+    let label = |row| GridCell::new(0, row).with_align(HAlign::Left, VAlign::Center);
+    let field = |row| GridCell::new(1, row).with_fill();
+    let cells = [
+        label(0),
+        field(0),
+        label(1),
+        field(1),
+        GridCell::new(0, 2).with_span(2, 1).with_align(HAlign::Right, VAlign::Bottom),
+    ];
+    let columns = [Track::Auto, Track::Fraction(1)];
+    let rows = [Track::Auto, Track::Auto, Track::Fraction(1)];
+    grid(
+        items.iter_mut().zip(cells.iter().copied()),
+        Rect::new_at_origin([200, 100]),
+        &columns,
+        &rows,
+        Size::new(8, 4),
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([0, 2], [40, 16]),
+            Rect::new([56, 0], [144, 20]),
+            Rect::new([0, 26], [48, 16]),
+            Rect::new([56, 24], [144, 20]),
+            Rect::new([140, 76], [60, 24]),
+        ]
+    );
+}
+
+#[test]
+fn grid_tracks() {
+    let mut items = rects(&[[30, 10], [20, 10], [70, 10], [20, 5], [20, 5]]);
+    let cells = [
+        GridCell::new(0, 0),
+        GridCell::new(1, 0),
+        // enlarges both auto columns
+        GridCell::new(0, 1).with_span(2, 1),
+        // clipped to the fixed column
+        GridCell::new(2, 0),
+        // outside the grid
+        GridCell::new(3, 0),
+    ];
+    let columns = [Track::Auto, Track::Auto, Track::Fixed(15)];
+    let rows = [Track::Auto, Track::Auto];
+    grid(
+        items.iter_mut().zip(cells.iter().copied()),
+        Rect::new_at_origin([200, 100]),
+        &columns,
+        &rows,
+        Size::new(10, 10),
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([0, 0], [30, 10]),
+            Rect::new([45, 0], [20, 10]),
+            Rect::new([0, 20], [70, 10]),
+            Rect::new([80, 0], [15, 5]),
+            Rect::new_at_origin([20, 5]),
+        ]
+    );
+}
+
+#[test]
+fn grid_fractions() {
+    let mut items = rects(&[[0, 0]; 3]);
+    let cells = [
+        GridCell::new(0, 0).with_fill(),
+        GridCell::new(1, 0).with_fill(),
+        GridCell::new(2, 0).with_fill(),
+    ];
+    let columns = [Track::Fraction(1), Track::Fixed(20), Track::Fraction(2)];
+    grid(
+        items.iter_mut().zip(cells.iter().copied()),
+        Rect::new([10, 10], [120, 30]),
+        &columns,
+        &[Track::Fraction(1)],
+        Size::new(5, 0),
+    );
+    let widths: Vec<_> = items.iter().map(|r| (r.pos.x, r.size.w)).collect();
+    assert_eq!(widths, vec![(10, 30), (45, 20), (70, 60)]);
+    assert!(items.iter().all(|r| r.size.h == 30));
+}
+
+#[test]
+fn flex_grow() {
+    let mut items = rects(&[[50, 10], [30, 20], [20, 10]]);
+    let flex_items = [
+        FlexItem::default(),
+        FlexItem::default().with_grow(1.0),
+        FlexItem::default().with_grow(3.0),
+    ];
+    let layout = FlexLayout::default().with_gap([10, 0]);
+    flex(
+        items.iter_mut().zip(flex_items.iter().copied()),
+        Rect::new_at_origin([200, 40]),
+        &layout,
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([0, 0], [50, 40]),
+            Rect::new([60, 0], [50, 40]),
+            Rect::new([120, 0], [80, 40]),
+        ]
+    );
+}
+
+#[test]
+fn flex_shrink() {
+    let mut items = rects(&[[10, 10], [50, 10], [20, 10]]);
+    // the shrink is scaled by the basis
+    let flex_items = [
+        FlexItem::default().with_basis(100),
+        FlexItem::default(),
+        FlexItem::default().with_shrink(0.0),
+    ];
+    let layout = FlexLayout::default().with_align_items(AlignItems::Start);
+    flex(
+        items.iter_mut().zip(flex_items.iter().copied()),
+        Rect::new_at_origin([120, 40]),
+        &layout,
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([0, 0], [67, 10]),
+            Rect::new([67, 0], [33, 10]),
+            Rect::new([100, 0], [20, 10]),
+        ]
+    );
+}
+
+#[test]
+fn flex_wrap() {
+    let mut items = rects(&[[40, 10], [40, 20], [40, 10], [30, 10]]);
+    let layout = FlexLayout::new(FlexDirection::Row)
+        .with_wrap(true)
+        .with_justify(Justify::SpaceBetween)
+        .with_align_items(AlignItems::Center)
+        .with_gap([10, 5]);
+    flex(
+        items.iter_mut().map(|item| (item, FlexItem::default())),
+        Rect::new_at_origin([100, 100]),
+        &layout,
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([0, 5], [40, 10]),
+            Rect::new([60, 0], [40, 20]),
+            Rect::new([0, 25], [40, 10]),
+            Rect::new([70, 25], [30, 10]),
+        ]
+    );
+}
+
+#[test]
+fn flex_column() {
+    let mut items = rects(&[[20, 10]; 3]);
+    let layout = FlexLayout::new(FlexDirection::Column)
+        .with_justify(Justify::SpaceEvenly)
+        .with_align_items(AlignItems::End);
+    flex(
+        items.iter_mut().map(|item| (item, FlexItem::default())),
+        Rect::new([10, 10], [50, 100]),
+        &layout,
+    );
+    assert_eq!(
+        items,
+        vec![
+            Rect::new([40, 28], [20, 10]),
+            Rect::new([40, 55], [20, 10]),
+            Rect::new([40, 83], [20, 10]),
+        ]
+    );
+}
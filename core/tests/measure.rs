@@ -0,0 +1,182 @@
+use rtk::geometry::{Border, SizeHint};
+use rtk::prelude::*;
+use rtk::testing::TestBackend;
+use rtk::toplevel::{TopLevel, Window};
+use rtk_derive::{Bounds, ObjectId, Visitable};
+
+/// Widget that prefers a size and records the rectangles it was arranged in.
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+struct Sized {
+    id: WidgetId,
+    bounds: Rect,
+    hint: SizeHint,
+    measured: Vec<Size>,
+    arranged: Vec<Rect>,
+}
+
+impl Sized {
+    fn new(hint: SizeHint) -> Self {
+        Sized {
+            id: WidgetId::new(),
+            bounds: Default::default(),
+            hint,
+            measured: vec![],
+            arranged: vec![],
+        }
+    }
+}
+
+impl Widget for Sized {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn measure<R: Resources>(&mut self, avail: Size, _resources: &mut R) -> SizeHint {
+        self.measured.push(avail);
+        self.hint
+    }
+
+    fn arrange<R: Resources>(&mut self, rect: Rect, _resources: &mut R) {
+        self.arranged.push(rect);
+        self.bounds = Rect::new(rect.pos, self.hint.clamp(rect.size));
+    }
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, _event: &Event, _ctx: EventContext) -> EventResult {
+        EventResult::Pass
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+/// Widget that only implements `update_layout`.
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+struct Legacy {
+    id: WidgetId,
+    bounds: Rect,
+    parent_rect: Option<Rect>,
+}
+
+impl Widget for Legacy {
+    fn update_layout<R: Resources>(&mut self, parent_rect: Rect, _resources: &mut R) {
+        self.parent_rect = Some(parent_rect);
+    }
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, _event: &Event, _ctx: EventContext) -> EventResult {
+        EventResult::Pass
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+/// Widget that only knows it's size after `update_layout`, like text that wraps to the parent width.
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+struct Wrapping {
+    id: WidgetId,
+    bounds: Rect,
+}
+
+impl Widget for Wrapping {
+    fn update_layout<R: Resources>(&mut self, parent_rect: Rect, _resources: &mut R) {
+        let w = parent_rect.size.w.min(120);
+        self.bounds.size = Size::new(w, 2400 / w);
+    }
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, _event: &Event, _ctx: EventContext) -> EventResult {
+        EventResult::Pass
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+#[test]
+fn size_hint_clamp() {
+    let hint = SizeHint::new([10, 10], [50, 20], [100, 30]);
+    assert_eq!(hint.clamp(Size::new(5, 25)), Size::new(10, 25));
+    assert_eq!(hint.clamp(Size::new(200, 40)), Size::new(100, 30));
+    assert_eq!(SizeHint::fixed([8, 8]).clamp(Size::new(20, 2)), Size::new(8, 8));
+    assert_eq!(SizeHint::flexible([8, 8]).clamp(Size::new(20, 2)), Size::new(20, 2));
+    assert_eq!(SizeHint::default(), SizeHint::flexible(Size::zero()));
+}
+
+#[test]
+fn size_hint_border() {
+    let hint = SizeHint::flexible([50, 20]).add_border(Border {
+        left: 1,
+        right: 2,
+        top: 3,
+        bottom: 4,
+    });
+    assert_eq!(hint.min, Size::new(3, 7));
+    assert_eq!(hint.preferred, Size::new(53, 27));
+    // unbounded stays unbounded
+    assert_eq!(hint.max, Size::unbounded());
+}
+
+#[test]
+fn window_natural_size() {
+    let mut window = Window::new(Sized::new(SizeHint::new([10, 10], [120, 40], [200, 200])));
+    window.update_layout(&mut TestBackend::default());
+    assert_eq!(window.size, Size::new(120, 40));
+    assert_eq!(window.child.measured, vec![Size::unbounded()]);
+    assert_eq!(window.child.arranged, vec![Rect::new_at_origin([120, 40])]);
+
+    // the window limits are respected
+    let mut window = Window::new(Sized::new(SizeHint::flexible([120, 40])));
+    window.set_min_size([50, 50]);
+    window.set_max_size([100, 100]);
+    window.update_layout(&mut TestBackend::default());
+    assert_eq!(window.size, Size::new(100, 50));
+    assert_eq!(window.child.measured, vec![Size::new(100, 100)]);
+    assert_eq!(window.child.get_size(), Size::new(100, 50));
+}
+
+#[test]
+fn window_fixed_size() {
+    let mut child = Sized::new(SizeHint::flexible([120, 40]));
+    child.set_position(Position::new(10, 5));
+    let mut window = Window::new(child);
+    window.set_size([300, 200]);
+    window.update_layout(&mut TestBackend::default());
+    // the child isn't measured, it takes the space after it's position
+    assert_eq!(window.size, Size::new(300, 200));
+    assert_eq!(window.child.measured, vec![]);
+    assert_eq!(window.child.arranged, vec![Rect::new([10, 5], [290, 195])]);
+}
+
+#[test]
+fn default_measure_arrange() {
+    let mut window = Window::new(Legacy {
+        id: WidgetId::new(),
+        bounds: Rect::new([20, 10], [60, 30]),
+        parent_rect: None,
+    });
+    window.update_layout(&mut TestBackend::default());
+    // the current size is used as a fixed hint, and the layout is updated with the window as parent
+    assert_eq!(window.size, Size::new(80, 40));
+    assert_eq!(window.child.parent_rect, Some(Rect::new_at_origin([80, 40])));
+}
+
+#[test]
+fn default_measure_updates_layout() {
+    let mut window = Window::new(Wrapping {
+        id: WidgetId::new(),
+        bounds: Default::default(),
+    });
+    window.update_layout(&mut TestBackend::default());
+    // the size is only known after running the layout
+    assert_eq!(window.size, Size::new(120, 20));
+    assert_eq!(window.child.get_size(), Size::new(120, 20));
+
+    // the available space is used as parent rect
+    let mut window = Window::new(Wrapping {
+        id: WidgetId::new(),
+        bounds: Default::default(),
+    });
+    window.set_max_size([60, 100]);
+    window.update_layout(&mut TestBackend::default());
+    assert_eq!(window.size, Size::new(60, 40));
+}
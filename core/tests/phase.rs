@@ -0,0 +1,164 @@
+use rtk::event::{Axis, ButtonState, EventPhase, MouseButton};
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+use rtk_derive::{Bounds, ObjectId, Visitable};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Log = Rc<RefCell<Vec<(&'static str, EventPhase, Event)>>>;
+
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+struct Child {
+    id: WidgetId,
+    bounds: Rect,
+    log: Log,
+    consume: bool,
+}
+
+impl Widget for Child {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        match event {
+            Event::MouseButton(_, _) | Event::MouseMoved(Axis::Position(_)) | Event::Character(_) => {
+                self.log.borrow_mut().push(("child", ctx.phase, event.clone()));
+                if self.consume {
+                    EventResult::ConsumedFocus
+                } else {
+                    EventResult::Pass
+                }
+            }
+            _ => EventResult::Pass,
+        }
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+struct Container {
+    id: WidgetId,
+    bounds: Rect,
+    #[visit_child]
+    child: Child,
+    log: Log,
+    steal_drag: bool,
+}
+
+impl Widget for Container {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn capture_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        // synthesized gestures are tested elsewhere
+        if let Event::Gesture(_) = event {
+            return EventResult::Pass;
+        }
+        self.log.borrow_mut().push(("container", ctx.phase, event.clone()));
+        match event {
+            Event::MouseMoved(_) if self.steal_drag && ctx.button_state.left() => EventResult::Consumed,
+            _ => EventResult::Pass,
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        match event {
+            Event::MouseButton(_, _) | Event::MouseMoved(Axis::Position(_)) | Event::Character(_) => {
+                self.log.borrow_mut().push(("container", ctx.phase, event.clone()));
+                EventResult::Consumed
+            }
+            _ => EventResult::Pass,
+        }
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+fn driver(consume: bool, steal_drag: bool) -> (EventDriver<Window<Container>>, Log) {
+    let log = Log::default();
+    let container = Container {
+        id: WidgetId::new(),
+        bounds: Rect::new_at_origin([100, 100]),
+        child: Child {
+            id: WidgetId::new(),
+            bounds: Rect::new([10, 10], [20, 20]),
+            log: log.clone(),
+            consume,
+        },
+        log: log.clone(),
+        steal_drag,
+    };
+    (EventDriver::new(Window::new(container)), log)
+}
+
+fn press() -> Event {
+    Event::MouseButton(ButtonState::Pressed, MouseButton::Left)
+}
+
+#[test]
+fn phase_capture_target_bubble() {
+    let (mut driver, log) = driver(false, false);
+    driver.move_to([15, 15]);
+    log.borrow_mut().clear();
+
+    driver.press(MouseButton::Left);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            ("container", EventPhase::Capture, press()),
+            ("child", EventPhase::Target, press()),
+            ("container", EventPhase::Bubble, press()),
+        ]
+    );
+    log.borrow_mut().clear();
+
+    // the container is the target when the pointer isn't over the child
+    driver.move_to([50, 50]);
+    let moved = Event::MouseMoved(Axis::Position([50.0, 50.0].into()));
+    assert_eq!(*log.borrow(), vec![("container", EventPhase::Target, moved)]);
+}
+
+#[test]
+fn phase_keyboard_focus() {
+    let (mut driver, log) = driver(true, false);
+    driver.click_at([15, 15], MouseButton::Left);
+    assert_eq!(driver.window().focused(), Some(driver.window().child.child.id));
+    log.borrow_mut().clear();
+
+    driver.push_event(Event::Character('a'));
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            ("container", EventPhase::Capture, Event::Character('a')),
+            ("child", EventPhase::Target, Event::Character('a')),
+        ]
+    );
+}
+
+#[test]
+fn phase_capture_steals_grab() {
+    let (mut driver, log) = driver(true, true);
+    let child_id = driver.window().child.child.id;
+    let container_id = driver.window().child.id;
+
+    driver.move_to([15, 15]);
+    driver.press(MouseButton::Left);
+    assert_eq!(driver.window().grabbed(), Some(child_id));
+    log.borrow_mut().clear();
+
+    driver.move_to([40, 40]);
+    let moved = Event::MouseMoved(Axis::Position([40.0, 40.0].into()));
+    assert_eq!(*log.borrow(), vec![("container", EventPhase::Capture, moved)]);
+    assert_eq!(driver.window().grabbed(), Some(container_id));
+    log.borrow_mut().clear();
+
+    // the release goes to the new grab holder and doesn't produce a click
+    driver.release(MouseButton::Left);
+    let release = Event::MouseButton(ButtonState::Released, MouseButton::Left);
+    assert_eq!(*log.borrow(), vec![("container", EventPhase::Target, release)]);
+    assert_eq!(driver.window().grabbed(), None);
+}
@@ -0,0 +1,91 @@
+mod common;
+use common::{pair_driver, Parent, Recorder};
+use rtk::event::{Axis, ScrollDelta, ScrollPhase};
+use rtk::geometry::Point;
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+
+fn driver() -> EventDriver<Window<Parent>> {
+    pair_driver(|event| matches!(event, Event::MouseMoved(Axis::Scroll(..))))
+}
+
+fn deltas(recorder: &Recorder) -> Vec<(ScrollDelta, ScrollPhase)> {
+    recorder
+        .events
+        .iter()
+        .filter_map(|(event, _)| match *event {
+            Event::MouseMoved(Axis::Scroll(delta, phase)) => Some((delta, phase)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn scroll(driver: &mut EventDriver<Window<Parent>>, dx: f64, dy: f64, phase: ScrollPhase) -> bool {
+    driver.push_event(Event::MouseMoved(Axis::Scroll(ScrollDelta::Pixels(dx, dy), phase)))
+}
+
+#[test]
+fn scroll_delta_to_pixels() {
+    assert_eq!(ScrollDelta::Lines(1.0, -2.0).to_pixels(16.0), Point::new(16.0, -32.0));
+    assert_eq!(ScrollDelta::Pixels(3.5, -7.0).to_pixels(16.0), Point::new(3.5, -7.0));
+}
+
+#[test]
+fn scroll_under_pointer() {
+    let mut driver = driver();
+    driver.move_to([15, 15]);
+    assert!(driver.scroll(0.0, 1.0));
+    assert!(driver.scroll_pixels(-4.0, 2.5));
+    driver.move_to([55, 15]);
+    assert!(driver.scroll_pixels(1.0, 0.0));
+    driver.move_to([40, 40]);
+    assert!(!driver.scroll(0.0, -1.0));
+
+    let window = driver.window();
+    assert_eq!(
+        deltas(&window.child.childs[0]),
+        vec![
+            (ScrollDelta::Lines(0.0, 1.0), ScrollPhase::Moved),
+            (ScrollDelta::Pixels(-4.0, 2.5), ScrollPhase::Moved),
+        ]
+    );
+    assert_eq!(
+        deltas(&window.child.childs[1]),
+        vec![(ScrollDelta::Pixels(1.0, 0.0), ScrollPhase::Moved)]
+    );
+}
+
+#[test]
+fn scroll_gesture_stays_on_target() {
+    let mut driver = driver();
+    driver.move_to([15, 15]);
+    assert!(scroll(&mut driver, 0.0, 1.0, ScrollPhase::Started));
+    // the pointer leaves the widget in the middle of the gesture
+    driver.move_to([55, 15]);
+    assert!(scroll(&mut driver, 0.0, 2.0, ScrollPhase::Moved));
+    driver.move_to([40, 40]);
+    assert!(scroll(&mut driver, 0.0, 0.0, ScrollPhase::Ended));
+    assert_eq!(deltas(&driver.window().child.childs[0]).len(), 3);
+    assert_eq!(deltas(&driver.window().child.childs[1]), vec![]);
+
+    // after the gesture ends, events go to the widget under the pointer again
+    driver.move_to([55, 15]);
+    assert!(scroll(&mut driver, 0.0, 1.0, ScrollPhase::Moved));
+    assert_eq!(deltas(&driver.window().child.childs[0]).len(), 3);
+    assert_eq!(
+        deltas(&driver.window().child.childs[1]),
+        vec![(ScrollDelta::Pixels(0.0, 1.0), ScrollPhase::Moved)]
+    );
+}
+
+#[test]
+fn scroll_gesture_start_not_consumed() {
+    let mut driver = driver();
+    driver.move_to([40, 40]);
+    assert!(!scroll(&mut driver, 0.0, 1.0, ScrollPhase::Started));
+    driver.move_to([55, 15]);
+    assert!(scroll(&mut driver, 0.0, 2.0, ScrollPhase::Moved));
+    assert_eq!(deltas(&driver.window().child.childs[0]), vec![]);
+    assert_eq!(deltas(&driver.window().child.childs[1]).len(), 1);
+}
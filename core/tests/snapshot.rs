@@ -0,0 +1,128 @@
+#![cfg(feature = "image")]
+use rtk::prelude::*;
+use rtk::testing::{Snapshot, SnapshotError};
+use rtk::toplevel::Window;
+use rtk::visitor::{Visitable, Visitor};
+
+/// Simple widget that draws some colored rectangles.
+#[derive(Debug, Clone, Copy)]
+struct Boxes {
+    bounds: Rect,
+    color: Color,
+}
+
+impl Boxes {
+    fn new(color: Color) -> Self {
+        Boxes {
+            bounds: Rect::new_at_origin([16, 16]),
+            color,
+        }
+    }
+}
+
+impl ObjectId for Boxes {
+    fn get_id(&self) -> WidgetId {
+        WidgetId::NONE
+    }
+}
+
+impl Bounds for Boxes {
+    fn get_position(&self) -> Position {
+        self.bounds.pos
+    }
+
+    fn get_size(&self) -> Size {
+        self.bounds.size
+    }
+}
+
+impl Visitable for Boxes {
+    fn accept<V: Visitor>(&mut self, visitor: V, prev_ctx: &V::Context) -> V {
+        if let Some(ctx) = visitor.new_context(self, prev_ctx) {
+            visitor.visit_before(self, &ctx).visit_after(self, &ctx)
+        } else {
+            visitor
+        }
+    }
+}
+
+impl Widget for Boxes {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
+
+    fn draw<B: DrawBackend>(&self, mut dc: DrawContext<B>) {
+        dc.draw_rect(Rect::new([2, 2], [6, 6]), self.color);
+        dc.draw_rect(Rect::new([8, 8], [6, 6]), Color::WHITE);
+        dc.draw_rect(Rect::new([4, 10], [4, 4]), Color::BLUE.with_alpha(0.5));
+    }
+
+    fn handle_event(&mut self, _event: &Event, _ctx: EventContext) -> EventResult {
+        EventResult::Pass
+    }
+
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
+
+fn snapshot_dir() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+}
+
+#[test]
+fn snapshot_matches() {
+    let mut window = Window::new(Boxes::new(Color::RED));
+    Snapshot::new(snapshot_dir(), [16, 16]).assert_matches("boxes", &mut window);
+}
+
+/// Copies the stored snapshot into a temporary directory, so failing comparisons can't touch the real one.
+fn temp_snapshot_dir(test: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("rtk-snapshot-{}-{}", test, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(snapshot_dir().join("boxes.png"), dir.join("boxes.png")).unwrap();
+    dir
+}
+
+#[test]
+fn snapshot_mismatch() {
+    let dir = temp_snapshot_dir("mismatch");
+    let mut window = Window::new(Boxes::new(Color::GREEN));
+    let mut snapshot = Snapshot::new(&dir, [16, 16]).with_update(false);
+    match snapshot.compare("boxes", &mut window) {
+        Err(SnapshotError::Mismatch {
+            pixels,
+            max_diff,
+            diff_path,
+        }) => {
+            assert_eq!(pixels, 36);
+            assert_eq!(max_diff, 255);
+            assert!(diff_path.exists());
+            assert!(dir.join("boxes.new.png").exists());
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // the difference is accepted with enough tolerance
+    let mut snapshot = snapshot.with_tolerance(255);
+    assert!(snapshot.compare("boxes", &mut window).is_ok());
+
+    match snapshot.compare("missing", &mut window) {
+        Err(SnapshotError::Missing(_)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn snapshot_size_mismatch() {
+    let dir = temp_snapshot_dir("size_mismatch");
+    let mut window = Window::new(Boxes::new(Color::RED));
+    let mut snapshot = Snapshot::new(&dir, [8, 8]).with_update(false);
+    match snapshot.compare("boxes", &mut window) {
+        Err(SnapshotError::SizeMismatch { expected, actual }) => {
+            assert_eq!(expected, Size::new(16, 16));
+            assert_eq!(actual, Size::new(8, 8));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
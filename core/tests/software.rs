@@ -0,0 +1,99 @@
+use rtk::backend::{DrawBackend, Resources};
+use rtk::draw::{Color, ColorOp, FillMode, TexCoord, TexRect, Text, TextSection};
+use rtk::geometry::{Point, Rect};
+use rtk::image::{Image, PixelFormat};
+use rtk::testing::{SoftwareBackend, TestVertex};
+
+fn vert(x: f32, y: f32, color: Color) -> TestVertex {
+    (Point::new(x, y), ColorOp::mul(color), TexCoord::default()).into()
+}
+
+#[test]
+fn software_rect() {
+    let mut backend = SoftwareBackend::new([8, 8]);
+    backend.clear(Color::BLACK);
+    let viewport = Rect::new_at_origin([8, 8]);
+
+    backend.draw_rect(Rect::new([2, 2], [4, 3]), Color::RED.into(), viewport);
+    assert_eq!(backend.get_pixel([1, 2]), Some(Color::BLACK));
+    assert_eq!(backend.get_pixel([2, 2]), Some(Color::RED));
+    assert_eq!(backend.get_pixel([5, 4]), Some(Color::RED));
+    assert_eq!(backend.get_pixel([6, 4]), Some(Color::BLACK));
+    assert_eq!(backend.get_pixel([5, 5]), Some(Color::BLACK));
+
+    // clipped against the viewport
+    backend.draw_rect(Rect::new([0, 0], [8, 8]), Color::BLUE.into(), Rect::new([0, 0], [1, 1]));
+    assert_eq!(backend.get_pixel([0, 0]), Some(Color::BLUE));
+    assert_eq!(backend.get_pixel([1, 0]), Some(Color::BLACK));
+}
+
+#[test]
+fn software_triangles() {
+    let mut backend = SoftwareBackend::new([4, 4]);
+    backend.clear(Color::BLACK);
+    let color = Color::WHITE.with_alpha(0.5);
+    let verts = [
+        vert(0.0, 0.0, color),
+        vert(4.0, 0.0, color),
+        vert(4.0, 4.0, color),
+        vert(0.0, 4.0, color),
+    ];
+
+    // the shared edge must not be drawn twice
+    backend.draw_triangles(
+        verts.iter().copied(),
+        [0, 1, 2, 2, 3, 0].iter().copied(),
+        None,
+        Rect::new_at_origin([4, 4]),
+    );
+    let image = backend.to_image();
+    assert_eq!(image.get_format(), PixelFormat::Rgba);
+    for y in 0..4 {
+        for x in 0..4 {
+            let c = backend.get_pixel([x, y]).unwrap();
+            assert!((c.r - 0.5).abs() < 1e-5, "pixel ({}, {}) = {:?}", x, y, c);
+        }
+    }
+}
+
+#[test]
+fn software_texture() {
+    let mut backend = SoftwareBackend::new([4, 4]);
+    let image = Image::new(
+        vec![255u8, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 255],
+        [2, 2],
+        PixelFormat::Rgba,
+    );
+    let texture = backend.create_texture(&image).unwrap();
+    let viewport = Rect::new_at_origin([4, 4]);
+
+    backend.draw_rect(viewport, texture.into(), viewport);
+    assert_eq!(backend.get_pixel([0, 0]), Some(Color::RED));
+    assert_eq!(backend.get_pixel([3, 0]), Some(Color::GREEN));
+    assert_eq!(backend.get_pixel([0, 3]), Some(Color::BLUE));
+    assert_eq!(backend.get_pixel([3, 3]), Some(Color::WHITE));
+
+    // only the bottom right texel, with a color op
+    let texr = TexRect::new(TexCoord::new(0.5, 0.5), TexCoord::BOTTOM_RIGHT);
+    let fill = FillMode::ColoredTexture(ColorOp::mul(Color::YELLOW), texture, texr);
+    backend.draw_rect(viewport, fill, viewport);
+    assert_eq!(backend.get_pixel([0, 0]), Some(Color::YELLOW));
+    assert_eq!(backend.get_pixel([3, 3]), Some(Color::YELLOW));
+}
+
+#[test]
+fn software_text() {
+    let mut backend = SoftwareBackend::new([64, 32]);
+    backend.clear(Color::BLACK);
+    if backend.enumerate_fonts().is_empty() {
+        return; // no fonts installed on this system
+    }
+    let text = TextSection::default().add_text(Text::new("Hi").with_scale(24.0).with_color([1.0, 1.0, 1.0, 1.0]));
+    backend.draw_text(text, Rect::new_at_origin([64, 32]));
+
+    let lit = (0..32)
+        .flat_map(|y| (0..64).map(move |x| [x, y]))
+        .filter(|&p| backend.get_pixel(p).unwrap().r > 0.5)
+        .count();
+    assert!(lit > 0);
+}
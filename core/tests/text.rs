@@ -0,0 +1,66 @@
+use rtk::draw::{Text, TextLayout, TextSection};
+use rtk::geometry::{HAlign, Point, VAlign};
+use rtk::prelude::*;
+use rtk::testing::{SoftwareBackend, TestBackend};
+
+fn section(text: &str) -> TextSection<'_> {
+    TextSection::default().add_text(Text::new(text).with_scale(20.0))
+}
+
+#[test]
+fn test_backend_measure() {
+    let mut backend = TestBackend::default();
+    assert_eq!(backend.measure_text(&section("hello")), Some(Rect::new([0, 0], [50, 20])));
+    assert_eq!(backend.measure_text(&section("")), None);
+    assert_eq!(backend.measure_text(&section("ab\ncde")), Some(Rect::new([0, 0], [30, 40])));
+
+    // alignment is relative to the screen position
+    let centered = section("abcd")
+        .with_screen_position((100.0, 50.0))
+        .with_layout(TextLayout::default_single_line().h_align(HAlign::Center).v_align(VAlign::Bottom));
+    assert_eq!(backend.measure_text(&centered), Some(Rect::new([80, 30], [40, 20])));
+
+    // wrapping at word boundaries
+    let wrapped = section("aaa bbb ccc").with_bounds((75.0, f32::INFINITY));
+    assert_eq!(backend.measure_text(&wrapped), Some(Rect::new([0, 0], [70, 40])));
+}
+
+#[test]
+fn test_backend_glyph_positions() {
+    let mut backend = TestBackend::default();
+    let text = TextSection::default()
+        .add_text(Text::new("ab").with_scale(10.0))
+        .add_text(Text::new("é!").with_scale(20.0))
+        .with_screen_position((5.0, 0.0));
+    let glyphs = backend.glyph_positions(&text);
+    let summary: Vec<_> = glyphs.iter().map(|g| (g.section_index, g.byte_index, g.pos.x)).collect();
+    assert_eq!(summary, vec![(0, 0, 5.0), (0, 1, 10.0), (1, 0, 15.0), (1, 2, 25.0)]);
+    assert_eq!(glyphs[3].size, Point::new(10.0, 20.0));
+    assert_eq!(glyphs[3].end_x(), 35.0);
+    assert_eq!(glyphs[0].bounds(), Rect::new([5, 0], [5, 10]));
+
+    // a wrapped line is only as tall as it's own glyphs
+    let text = TextSection::default()
+        .add_text(Text::new("aa ").with_scale(10.0))
+        .add_text(Text::new("bbb").with_scale(20.0))
+        .with_bounds((40.0, f32::INFINITY));
+    let glyphs = backend.glyph_positions(&text);
+    let summary: Vec<_> = glyphs.iter().map(|g| (g.section_index, g.byte_index, g.pos.y)).collect();
+    assert_eq!(summary, vec![(0, 0, 0.0), (0, 1, 0.0), (1, 0, 10.0), (1, 1, 10.0), (1, 2, 10.0)]);
+    assert_eq!(backend.measure_text(&text), Some(Rect::new([0, 0], [30, 30])));
+}
+
+#[test]
+fn software_backend_metrics() {
+    let mut backend = SoftwareBackend::new([16, 16]);
+    let text = section("hello world");
+    let glyphs = backend.glyph_positions(&text);
+    let bounds = match backend.measure_text(&text) {
+        Some(bounds) => bounds,
+        None => return, // no system fonts available
+    };
+    assert_eq!(glyphs.len(), 11);
+    assert!(glyphs.windows(2).all(|g| g[0].pos.x < g[1].pos.x));
+    assert!(glyphs.iter().all(|g| g.pos.y == glyphs[0].pos.y));
+    assert!(bounds.w() as f32 >= glyphs[10].end_x() - glyphs[0].pos.x - 1.0);
+}
@@ -0,0 +1,114 @@
+mod common;
+use common::Recorder;
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::timer::{self, TimerId};
+use rtk::toplevel::Window;
+use std::time::{Duration, Instant};
+
+fn driver() -> EventDriver<Window<Recorder>> {
+    let recorder = Recorder::new(Rect::new_at_origin([10, 10]), |event| {
+        matches!(event, Event::Timer(_) | Event::AnimationFrame(_))
+    });
+    EventDriver::new(Window::new(recorder))
+}
+
+fn timers(driver: &EventDriver<Window<Recorder>>) -> Vec<TimerId> {
+    driver
+        .window()
+        .child
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Timer(id) => Some(id),
+            _ => None,
+        })
+        .collect()
+}
+
+fn frames(driver: &EventDriver<Window<Recorder>>) -> Vec<Duration> {
+    driver
+        .window()
+        .child
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::AnimationFrame(elapsed) => Some(elapsed),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn timer_timeout() {
+    let mut driver = driver();
+    let start = Instant::now();
+    let target = driver.window().child.id;
+    let t1 = timer::set_timeout(target, Duration::from_millis(100));
+    let t2 = timer::set_timeout(target, Duration::from_millis(50));
+    let t3 = timer::set_timeout(target, Duration::from_millis(70));
+    assert!(timer::next_deadline().unwrap() >= start + Duration::from_millis(50));
+
+    assert!(!driver.process_timers(start));
+    assert!(timer::cancel_timer(t3));
+    assert!(!timer::cancel_timer(t3));
+
+    assert!(driver.process_timers(start + Duration::from_secs(1)));
+    assert_eq!(timers(&driver), vec![t2, t1]);
+    assert_eq!(timer::next_deadline(), None);
+}
+
+#[test]
+fn timer_interval() {
+    let mut driver = driver();
+    let start = Instant::now();
+    let target = driver.window().child.id;
+    let id = timer::set_interval(target, Duration::from_millis(10));
+
+    driver.process_timers(start + Duration::from_millis(15));
+    // missed ticks are skipped
+    driver.process_timers(start + Duration::from_millis(100));
+    assert_eq!(timers(&driver), vec![id, id]);
+    assert!(timer::next_deadline().unwrap() > start + Duration::from_millis(100));
+
+    timer::cancel_timer(id);
+    assert_eq!(timer::next_deadline(), None);
+}
+
+#[test]
+fn timer_animation_frame() {
+    let mut driver = driver();
+    let target = driver.window().child.id;
+    assert!(!timer::animation_pending());
+
+    timer::request_animation_frame(target);
+    timer::request_animation_frame(target);
+    assert!(timer::animation_pending());
+
+    let now = Instant::now() + Duration::from_millis(16);
+    assert!(driver.process_timers(now));
+    assert!(!timer::animation_pending());
+    let frames = frames(&driver);
+    assert_eq!(frames.len(), 1);
+    assert!(frames[0] >= Duration::from_millis(16));
+}
+
+#[test]
+fn timer_animation_frame_interval() {
+    let mut driver = driver();
+    let target = driver.window().child.id;
+    timer::request_animation_frame(target);
+    let start = Instant::now();
+    // the first frame is due right away
+    assert!(timer::next_deadline().unwrap() <= start);
+    assert!(driver.process_timers(start));
+
+    // requested right after a frame, the next one waits for the frame interval
+    timer::request_animation_frame(target);
+    assert_eq!(timer::next_deadline(), Some(start + timer::FRAME_INTERVAL));
+    assert!(!driver.process_timers(start + timer::FRAME_INTERVAL / 2));
+    assert!(timer::animation_pending());
+    assert!(driver.process_timers(start + timer::FRAME_INTERVAL));
+    assert_eq!(frames(&driver).len(), 2);
+    assert!(!timer::animation_pending());
+}
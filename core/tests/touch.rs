@@ -0,0 +1,95 @@
+mod common;
+use common::{pair_driver, Parent, Recorder};
+use rtk::event::{TouchId, TouchPhase};
+use rtk::geometry::Point;
+use rtk::prelude::*;
+use rtk::testing::EventDriver;
+use rtk::toplevel::Window;
+
+fn driver() -> EventDriver<Window<Parent>> {
+    pair_driver(|event| matches!(event, Event::Touch { .. }))
+}
+
+fn touches(recorder: &Recorder) -> Vec<(TouchId, TouchPhase, Point<f64>)> {
+    recorder
+        .events
+        .iter()
+        .filter_map(|(event, pos)| match *event {
+            Event::Touch { id, phase, .. } => Some((id, phase, *pos)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn phases(touches: &[(TouchId, TouchPhase, Point<f64>)]) -> Vec<(TouchId, TouchPhase)> {
+    touches.iter().map(|&(id, phase, _)| (id, phase)).collect()
+}
+
+#[test]
+fn touch_initial_contact() {
+    let mut driver = driver();
+    let left_id = driver.window().child.childs[0].id;
+
+    assert!(driver.touch(1, TouchPhase::Started, [15, 15]));
+    assert_eq!(driver.window().dispatcher().touch_target(1), Some(left_id));
+    // the contact keeps going to the same widget after leaving it
+    assert!(driver.touch(1, TouchPhase::Moved, [55, 20]));
+    assert!(driver.touch(1, TouchPhase::Ended, [90, 45]));
+    assert_eq!(driver.window().dispatcher().touch_target(1), None);
+
+    let window = driver.window();
+    assert_eq!(touches(&window.child.childs[1]), vec![]);
+    assert_eq!(
+        touches(&window.child.childs[0]),
+        vec![
+            (1, TouchPhase::Started, [5.0, 5.0].into()),
+            (1, TouchPhase::Moved, [45.0, 10.0].into()),
+            (1, TouchPhase::Ended, [80.0, 35.0].into()),
+        ]
+    );
+    // touches don't move the mouse pointer
+    assert_eq!(driver.pointer_pos(), None);
+}
+
+#[test]
+fn touch_multiple_contacts() {
+    let mut driver = driver();
+    let left_id = driver.window().child.childs[0].id;
+    let right_id = driver.window().child.childs[1].id;
+
+    driver.touch(1, TouchPhase::Started, [15, 15]);
+    driver.touch(2, TouchPhase::Started, [65, 25]);
+    assert_eq!(driver.window().dispatcher().touch_target(1), Some(left_id));
+    assert_eq!(driver.window().dispatcher().touch_target(2), Some(right_id));
+
+    driver.touch(2, TouchPhase::Moved, [20, 20]);
+    driver.touch(1, TouchPhase::Moved, [60, 20]);
+    driver.touch(2, TouchPhase::Cancelled, [20, 20]);
+    assert_eq!(driver.window().dispatcher().touch_target(2), None);
+    driver.touch(1, TouchPhase::Ended, [60, 20]);
+
+    let window = driver.window();
+    assert_eq!(
+        phases(&touches(&window.child.childs[0])),
+        [(1, TouchPhase::Started), (1, TouchPhase::Moved), (1, TouchPhase::Ended)]
+    );
+    assert_eq!(
+        phases(&touches(&window.child.childs[1])),
+        [(2, TouchPhase::Started), (2, TouchPhase::Moved), (2, TouchPhase::Cancelled)]
+    );
+}
+
+#[test]
+fn touch_outside_widgets() {
+    let mut driver = driver();
+    assert!(!driver.touch(1, TouchPhase::Started, [40, 40]));
+    assert_eq!(driver.window().dispatcher().touch_target(1), None);
+    // moving into a widget doesn't deliver the contact to it
+    assert!(!driver.touch(1, TouchPhase::Moved, [15, 15]));
+    assert!(!driver.touch(1, TouchPhase::Ended, [15, 15]));
+    assert_eq!(touches(&driver.window().child.childs[0]), vec![]);
+
+    assert!(driver.touch_drag(1, [55, 15], [15, 15]));
+    assert_eq!(touches(&driver.window().child.childs[0]), vec![]);
+    assert_eq!(touches(&driver.window().child.childs[1]).len(), 6);
+}
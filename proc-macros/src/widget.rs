@@ -26,6 +26,20 @@ pub fn widget_impl(mut input: DeriveInput) -> TokenStream {
                         }
                     }
 
+                    #[inline]
+                    fn measure<R: #crate_::backend::Resources>(&mut self, avail: #crate_::geometry::Size, resources: &mut R) -> #crate_::geometry::SizeHint {
+                        match self {
+                            #(#patterns => #path::Widget::measure(a, avail, resources),)*
+                        }
+                    }
+
+                    #[inline]
+                    fn arrange<R: #crate_::backend::Resources>(&mut self, rect: #crate_::geometry::Rect, resources: &mut R) {
+                        match self {
+                            #(#patterns => #path::Widget::arrange(a, rect, resources),)*
+                        }
+                    }
+
                     #[inline]
                     fn draw<B: #crate_::backend::DrawBackend>(&self, dc: #crate_::draw::DrawContext<B>) {
                         match self {
@@ -40,6 +54,13 @@ pub fn widget_impl(mut input: DeriveInput) -> TokenStream {
                         }
                     }
 
+                    #[inline]
+                    fn capture_event(&mut self, event: &#pevent::Event, ctx: #pevent::EventContext) -> #pevent::EventResult {
+                        match self {
+                            #(#patterns => #path::Widget::capture_event(a, event, ctx),)*
+                        }
+                    }
+
                     #[inline]
                     fn event_consumed(&mut self, event: &#pevent::Event, ctx: &#pevent::EventContext) {
                         match self {
@@ -60,6 +81,27 @@ pub fn widget_impl(mut input: DeriveInput) -> TokenStream {
                             #(#patterns => #path::Widget::is_clipped(a),)*
                         }
                     }
+
+                    #[inline]
+                    fn accepts_focus(&self) -> bool {
+                        match self {
+                            #(#patterns => #path::Widget::accepts_focus(a),)*
+                        }
+                    }
+
+                    #[inline]
+                    fn ime_cursor_area(&self) -> Option<#crate_::geometry::Rect> {
+                        match self {
+                            #(#patterns => #path::Widget::ime_cursor_area(a),)*
+                        }
+                    }
+
+                    #[inline]
+                    fn tab_index(&self) -> u32 {
+                        match self {
+                            #(#patterns => #path::Widget::tab_index(a),)*
+                        }
+                    }
                 }
             }
         }),
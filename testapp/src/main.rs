@@ -1,3 +1,4 @@
+use rtk::animation::{Animated, Easing};
 use rtk::draw::{Text, TextLayout, TextSection, TextureId};
 use rtk::event::*;
 use rtk::geometry::{HAlign, VAlign};
@@ -7,13 +8,14 @@ use rtk::prelude::*;
 use rtk::toplevel::Window;
 use rtk_derive::{Bounds, ObjectId, Visitable};
 use rtk_glium::GliumApplication;
+use std::time::Duration;
 
 #[derive(Debug, ObjectId, Bounds, Visitable)]
 struct TestWidget {
     bounds: Rect,
     color: Color,
     id: WidgetId,
-    hover: bool,
+    hover: Animated<Color>,
     vp_orig: Position,
     texture: TextureId,
     #[visit_iter]
@@ -45,9 +47,7 @@ impl Widget for TestWidget {
     }
 
     fn draw<B: DrawBackend>(&self, mut dc: DrawContext<B>) {
-        let hover = if self.hover { Color::gray(0.05) } else { Default::default() };
-
-        dc.draw_rect((dc.origin(), self.bounds.size), self.texture * self.color + hover);
+        dc.draw_rect((dc.origin(), self.bounds.size), self.texture * self.color + *self.hover.get());
         dc.draw_triangle([10, 110], [100, 150], [50, 200], Color::BLUE.with_alpha(0.5));
         dc.draw_text(
             TextSection::default()
@@ -68,7 +68,7 @@ impl Widget for TestWidget {
             Event::MouseButton(Pressed, MouseButton::Left) => {
                 println!("TestWidget({:?}) clicked! (pos={:?})", self.id, ctx.local_pos);
                 self.color = Color::WHITE;
-                EventResult::Consumed
+                EventResult::ConsumedFocus
             }
             Event::Keyboard { state: Pressed, key, .. } => {
                 match key {
@@ -81,9 +81,17 @@ impl Widget for TestWidget {
                 EventResult::Consumed
             }
             Event::PointerInside(inside) => {
-                self.hover = *inside;
+                let hover = if *inside { Color::gray(0.05) } else { Default::default() };
+                self.hover.animate_to(hover, self.id);
                 EventResult::Consumed
             }
+            Event::AnimationFrame(elapsed) => {
+                if self.hover.tick(*elapsed, self.id) {
+                    EventResult::Consumed
+                } else {
+                    EventResult::Pass
+                }
+            }
             _ => EventResult::Pass,
         }
     }
@@ -99,6 +107,10 @@ impl Widget for TestWidget {
     fn viewport_origin(&self) -> Position {
         self.vp_orig
     }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Debug, ObjectId, Bounds, Visitable)]
@@ -162,7 +174,7 @@ fn main() {
     let mut widget = TestWidget {
         bounds: Rect::new([20, 10], [320, 240]),
         color: Color::WHITE,
-        hover: false,
+        hover: Animated::new(Default::default(), Duration::from_millis(150), Easing::EASE_OUT),
         vp_orig: Default::default(),
         id: WidgetId::new(),
         texture,
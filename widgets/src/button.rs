@@ -0,0 +1,340 @@
+use crate::Label;
+use rtk::draw::TextureId;
+use rtk::event::{ButtonState, Key, MouseButton};
+use rtk::geometry::{Border, SizeHint};
+use rtk::prelude::*;
+use rtk_derive::{Bounds, ObjectId, Visitable};
+
+/// Colors and spacing used to draw a button.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonStyle {
+    /// Background color in the normal state.
+    pub normal: Color,
+    /// Background color while the pointer is over the button.
+    pub hover: Color,
+    /// Background color while the button is held down.
+    pub pressed: Color,
+    /// Background color while the button is disabled.
+    pub disabled: Color,
+    /// Border color while the button has keyboard focus.
+    pub focus: Color,
+    /// Width of the focus border.
+    pub focus_width: u32,
+    /// Space between the button edges and it's contents.
+    pub padding: Border,
+    /// Space between the image and the text.
+    pub spacing: u32,
+}
+
+impl Default for ButtonStyle {
+    fn default() -> Self {
+        ButtonStyle {
+            normal: Color::gray(0.6),
+            hover: Color::gray(0.7),
+            pressed: Color::gray(0.45),
+            disabled: Color::gray(0.3),
+            focus: Color::BLUE,
+            focus_width: 1,
+            padding: Border::symmetric(8, 4),
+            spacing: 4,
+        }
+    }
+}
+
+/// The visual state of a button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ButtonStatus {
+    Normal,
+    Hover,
+    Pressed,
+    Disabled,
+}
+
+/// A push button with text and/or image content.
+///
+/// When the button is activated (clicked or Space/Enter pressed while focused) it returns
+/// `ConsumedNotifyTarget`, so the target widget (the parent by default) receives the triggering event
+/// on `Widget::event_consumed` with `EventContext::widget` set to the button id.
+#[derive(Debug, Clone, PartialEq, ObjectId, Bounds, Visitable)]
+pub struct Button {
+    id: WidgetId,
+    bounds: Rect,
+    label: Label,
+    image: Option<(TextureId, Rect)>,
+    style: ButtonStyle,
+    fixed_size: Option<Size>,
+    target: Option<WidgetId>,
+    enabled: bool,
+    hover: bool,
+    pressed: bool,
+    focused: bool,
+}
+
+impl Button {
+    /// Creates a button with a text label.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::from_label(Label::new(text))
+    }
+
+    /// Creates a button with a custom label.
+    pub fn from_label(label: Label) -> Self {
+        Button {
+            id: WidgetId::new(),
+            bounds: Default::default(),
+            label,
+            image: None,
+            style: Default::default(),
+            fixed_size: None,
+            target: None,
+            enabled: true,
+            hover: false,
+            pressed: false,
+            focused: false,
+        }
+    }
+
+    /// Creates a button that only shows an image.
+    pub fn from_image(texture: TextureId, size: impl Into<Size>) -> Self {
+        Self::new("").with_image(texture, size)
+    }
+
+    /// Adds an image to the left of the text.
+    #[inline]
+    pub fn with_image(self, texture: TextureId, size: impl Into<Size>) -> Self {
+        Button {
+            image: Some((texture, Rect::new_at_origin(size))),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn with_style(self, style: ButtonStyle) -> Self {
+        Button { style, ..self }
+    }
+
+    /// Sets a fixed size for the button, instead of taking the size of it's contents.
+    #[inline]
+    pub fn with_fixed_size(self, size: impl Into<Size>) -> Self {
+        Button {
+            fixed_size: Some(size.into()),
+            ..self
+        }
+    }
+
+    /// Sets the widget that is notified when the button is activated, instead of the parent.
+    #[inline]
+    pub fn with_target(self, target: WidgetId) -> Self {
+        Button {
+            target: Some(target),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Button { enabled, ..self }
+    }
+
+    #[inline]
+    pub fn get_label(&self) -> &Label {
+        &self.label
+    }
+
+    #[inline]
+    pub fn get_label_mut(&mut self) -> &mut Label {
+        &mut self.label
+    }
+
+    #[inline]
+    pub fn get_style(&self) -> &ButtonStyle {
+        &self.style
+    }
+
+    #[inline]
+    pub fn set_style(&mut self, style: ButtonStyle) {
+        self.style = style;
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the button. A disabled button ignores all input.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hover = false;
+            self.pressed = false;
+        }
+    }
+
+    /// Checks if the button has keyboard focus.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Gets the current visual state.
+    pub fn status(&self) -> ButtonStatus {
+        if !self.enabled {
+            ButtonStatus::Disabled
+        } else if self.pressed {
+            ButtonStatus::Pressed
+        } else if self.hover {
+            ButtonStatus::Hover
+        } else {
+            ButtonStatus::Normal
+        }
+    }
+
+    /// Lays out the contents inside the available space.
+    fn layout<R: Resources>(&mut self, avail: Size, resources: &mut R) {
+        let padding = self.style.padding;
+        let avail = self.fixed_size.unwrap_or(avail);
+        let (image_size, spacing) = self.image_space();
+        let has_text = !self.label.get_text().is_empty();
+
+        let text_avail = avail.remove_border(padding).saturating_sub(Size::new(image_size.w + spacing, 0));
+        self.label.arrange(Rect::new_at_origin(text_avail), resources);
+        let text_size = if has_text { self.label.get_size() } else { Default::default() };
+
+        let content = Size::new(image_size.w + spacing + text_size.w, image_size.h.max(text_size.h));
+        self.bounds.size = self.fixed_size.unwrap_or_else(|| content.add_border(padding));
+
+        // center the contents inside the button
+        let origin = Position::new(
+            (self.bounds.size.w as i32 - content.w as i32) / 2,
+            (self.bounds.size.h as i32 - content.h as i32) / 2,
+        );
+        if let Some((_, rect)) = &mut self.image {
+            rect.pos = origin.offset(0, (content.h - image_size.h) as i32 / 2);
+        }
+        self.label
+            .set_position(origin.offset((image_size.w + spacing) as i32, (content.h - text_size.h) as i32 / 2));
+    }
+
+    /// Gets the size of the image and the spacing between it and the text.
+    fn image_space(&self) -> (Size, u32) {
+        let image_size = self.image.map_or_else(Default::default, |(_, rect)| rect.size);
+        let has_text = !self.label.get_text().is_empty();
+        let spacing = if self.image.is_some() && has_text { self.style.spacing } else { 0 };
+        (image_size, spacing)
+    }
+
+    /// Notifies the activation to the target widget.
+    #[inline]
+    fn activate(&self, ctx: &EventContext) -> EventResult {
+        EventResult::ConsumedNotifyTarget(self.target.unwrap_or(ctx.parent))
+    }
+}
+
+impl Widget for Button {
+    fn update_layout<R: Resources>(&mut self, parent_rect: Rect, resources: &mut R) {
+        self.layout(parent_rect.size.saturating_sub(self.bounds.pos.as_size()), resources);
+    }
+
+    fn measure<R: Resources>(&mut self, avail: Size, resources: &mut R) -> SizeHint {
+        if let Some(size) = self.fixed_size {
+            return SizeHint::fixed(size);
+        }
+        let (image_size, spacing) = self.image_space();
+        let text = if self.label.get_text().is_empty() {
+            SizeHint::fixed(Size::zero())
+        } else {
+            let text_avail = avail
+                .remove_border(self.style.padding)
+                .saturating_sub(Size::new(image_size.w + spacing, 0));
+            self.label.measure(text_avail, resources)
+        };
+        let content = |text: Size| Size::new(image_size.w + spacing + text.w, image_size.h.max(text.h));
+        SizeHint::new(content(text.min), content(text.preferred), content(text.max)).add_border(self.style.padding)
+    }
+
+    fn arrange<R: Resources>(&mut self, rect: Rect, resources: &mut R) {
+        self.bounds.pos = rect.pos;
+        self.layout(rect.size, resources);
+    }
+    fn draw<B: DrawBackend>(&self, mut dc: DrawContext<B>) {
+        let style = &self.style;
+        let background = match self.status() {
+            ButtonStatus::Normal => style.normal,
+            ButtonStatus::Hover => style.hover,
+            ButtonStatus::Pressed => style.pressed,
+            ButtonStatus::Disabled => style.disabled,
+        };
+        let rect = Rect::new_at_origin(self.bounds.size);
+        dc.draw_rect(rect, background);
+        if self.focused {
+            Border::all(style.focus_width).calc_rects(rect, |r| dc.draw_rect(r, style.focus));
+        }
+        if let Some((texture, rect)) = self.image {
+            dc.draw_rect(rect, texture);
+        }
+        if !self.label.get_text().is_empty() {
+            dc.draw_child(&self.label);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        if !self.enabled {
+            return EventResult::Pass;
+        }
+        match event {
+            Event::PointerInside(inside) => {
+                self.hover = *inside;
+                EventResult::Consumed
+            }
+            Event::MouseButton(ButtonState::Pressed, MouseButton::Left) => {
+                self.pressed = true;
+                EventResult::ConsumedFocus
+            }
+            Event::MouseButton(ButtonState::Released, MouseButton::Left) => {
+                self.pressed = false;
+                EventResult::Consumed
+            }
+            Event::Click(MouseButton::Left) => self.activate(&ctx),
+            Event::Keyboard { state, key, .. } if self.focused && is_activation_key(*key) => match state {
+                ButtonState::Pressed => {
+                    self.pressed = true;
+                    EventResult::Consumed
+                }
+                ButtonState::Released if self.pressed => {
+                    self.pressed = false;
+                    self.activate(&ctx)
+                }
+                ButtonState::Released => EventResult::Pass,
+            },
+            Event::FocusGained => {
+                self.focused = true;
+                EventResult::Consumed
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.pressed = false;
+                EventResult::Consumed
+            }
+            _ => EventResult::Pass,
+        }
+    }
+
+    #[inline]
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+
+    #[inline]
+    fn accepts_focus(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[inline]
+fn is_activation_key(key: Key) -> bool {
+    matches!(key, Key::Space | Key::Enter(_))
+}
+
+impl From<Label> for Button {
+    #[inline]
+    fn from(label: Label) -> Self {
+        Button::from_label(label)
+    }
+}
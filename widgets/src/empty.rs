@@ -1,3 +1,4 @@
+use rtk::geometry::SizeHint;
 use rtk::prelude::*;
 use rtk_derive::{Bounds, Visitable};
 
@@ -40,6 +41,16 @@ impl Widget for Empty {
     #[inline]
     fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, _resources: &mut R) {}
 
+    #[inline]
+    fn measure<R: Resources>(&mut self, _avail: Size, _resources: &mut R) -> SizeHint {
+        SizeHint::flexible(self.bounds.size)
+    }
+
+    #[inline]
+    fn arrange<R: Resources>(&mut self, rect: Rect, _resources: &mut R) {
+        self.bounds = rect;
+    }
+
     #[inline]
     fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
 
@@ -0,0 +1,242 @@
+use rtk::draw::{Text, TextLayout, TextSection};
+use rtk::font::FontId;
+use rtk::geometry::{AlignValue, Alignment, Point, SizeHint};
+use rtk::prelude::*;
+use rtk_derive::{Bounds, Visitable};
+
+/// Text appended to truncated labels.
+pub const ELLIPSIS: &str = "…";
+
+/// Defines how a label handles text that doesn't fit in it's bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextOverflow {
+    /// The text is cut at the label bounds.
+    Clip,
+    /// The text is wrapped into multiple lines.
+    Wrap,
+    /// The text is truncated and an ellipsis is appended.
+    Ellipsis,
+}
+
+impl Default for TextOverflow {
+    #[inline]
+    fn default() -> Self {
+        TextOverflow::Clip
+    }
+}
+
+/// A widget that displays some text.
+///
+/// By default the label takes the size of it's text, limited by the space available in the parent.
+#[derive(Debug, Clone, PartialEq, Bounds, Visitable)]
+pub struct Label {
+    bounds: Rect,
+    text: String,
+    font: FontId,
+    font_size: f32,
+    color: Color,
+    align: Alignment,
+    overflow: TextOverflow,
+    fixed_size: Option<Size>,
+    /// Length of the text that is shown before the ellipsis.
+    truncated: Option<usize>,
+}
+
+impl Label {
+    /// Creates a label with the default font.
+    pub fn new(text: impl Into<String>) -> Self {
+        Label {
+            bounds: Default::default(),
+            text: text.into(),
+            font: Default::default(),
+            font_size: 16.0,
+            color: Color::BLACK,
+            align: Default::default(),
+            overflow: Default::default(),
+            fixed_size: None,
+            truncated: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_font(self, font: FontId) -> Self {
+        Label { font, ..self }
+    }
+
+    #[inline]
+    pub fn with_font_size(self, font_size: f32) -> Self {
+        Label { font_size, ..self }
+    }
+
+    #[inline]
+    pub fn with_color(self, color: impl Into<Color>) -> Self {
+        Label {
+            color: color.into(),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn with_align(self, align: impl Into<Alignment>) -> Self {
+        Label {
+            align: align.into(),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn with_overflow(self, overflow: TextOverflow) -> Self {
+        Label { overflow, ..self }
+    }
+
+    /// Sets a fixed size for the label, instead of taking the size of it's text.
+    #[inline]
+    pub fn with_fixed_size(self, size: impl Into<Size>) -> Self {
+        Label {
+            fixed_size: Some(size.into()),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    #[inline]
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.truncated = None;
+    }
+
+    #[inline]
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    #[inline]
+    pub fn set_color(&mut self, color: impl Into<Color>) {
+        self.color = color.into();
+    }
+
+    /// Gets the text that is currently displayed, after the ellipsis truncation.
+    pub fn displayed_text(&self) -> String {
+        match self.truncated {
+            Some(len) => format!("{}{}", &self.text[..len], ELLIPSIS),
+            None => self.text.clone(),
+        }
+    }
+
+    /// Builds a text section for a part of the text, optionally followed by an ellipsis.
+    fn section<'a>(&self, text: &'a str, ellipsis: bool, bounds: Point<f32>, align: Alignment) -> TextSection<'a> {
+        let make_text = |s| {
+            Text::new(s)
+                .with_scale(self.font_size)
+                .with_color(self.color)
+                .with_font_id(self.font)
+        };
+        let layout = if self.overflow == TextOverflow::Wrap {
+            TextLayout::default_wrap()
+        } else {
+            TextLayout::default_single_line()
+        };
+        let section = TextSection::default()
+            .add_text(make_text(text))
+            .with_bounds(bounds)
+            .with_layout(layout.h_align(align.horizontal).v_align(align.vertical));
+        if ellipsis {
+            section.add_text(make_text(ELLIPSIS))
+        } else {
+            section
+        }
+    }
+
+    /// Measures a part of the text, returning the size it needs when drawn at the origin.
+    fn text_size<R: Resources>(&self, len: usize, ellipsis: bool, max_width: u32, resources: &mut R) -> Size {
+        let bounds = Point::new(max_width as f32, f32::INFINITY);
+        let section = self.section(&self.text[..len], ellipsis, bounds, Default::default());
+        resources.measure_text(&section).map_or_else(Default::default, |rect| {
+            let end = rect.pos + rect.size.as_point();
+            Size::new(end.x.max(0) as u32, end.y.max(0) as u32)
+        })
+    }
+
+    /// Sizes the label to it's text, limited by the available space.
+    fn layout<R: Resources>(&mut self, avail: Size, resources: &mut R) {
+        let avail = self.fixed_size.unwrap_or(avail);
+
+        self.truncated = None;
+        let mut size = self.text_size(self.text.len(), false, avail.w, resources);
+        if self.overflow == TextOverflow::Ellipsis && size.w > avail.w {
+            let len = self.fit_ellipsis(avail.w, resources);
+            size = self.text_size(len, true, avail.w, resources);
+            self.truncated = Some(len);
+        }
+
+        self.bounds.size = self
+            .fixed_size
+            .unwrap_or_else(|| Size::new(size.w.min(avail.w), size.h.min(avail.h)));
+    }
+
+    /// Finds the longest part of the text that fits in the specified width when followed by an ellipsis.
+    fn fit_ellipsis<R: Resources>(&self, max_width: u32, resources: &mut R) -> usize {
+        let prefix_len = |n| self.text.char_indices().nth(n).map_or(self.text.len(), |(i, _)| i);
+        let (mut lo, mut hi) = (0, self.text.chars().count());
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if self.text_size(prefix_len(mid), true, max_width, resources).w <= max_width {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        prefix_len(lo)
+    }
+}
+
+impl ObjectId for Label {
+    #[inline]
+    fn get_id(&self) -> WidgetId {
+        WidgetId::NONE
+    }
+}
+
+impl Widget for Label {
+    fn update_layout<R: Resources>(&mut self, parent_rect: Rect, resources: &mut R) {
+        self.layout(parent_rect.size.saturating_sub(self.bounds.pos.as_size()), resources);
+    }
+
+    fn measure<R: Resources>(&mut self, avail: Size, resources: &mut R) -> SizeHint {
+        if let Some(size) = self.fixed_size {
+            return SizeHint::fixed(size);
+        }
+        let size = self.text_size(self.text.len(), false, avail.w, resources);
+        let min = if self.overflow == TextOverflow::Ellipsis {
+            self.text_size(0, true, avail.w, resources)
+        } else {
+            size
+        };
+        SizeHint::new(min, size, size)
+    }
+
+    fn arrange<R: Resources>(&mut self, rect: Rect, resources: &mut R) {
+        self.bounds.pos = rect.pos;
+        self.layout(rect.size, resources);
+    }
+
+    fn draw<B: DrawBackend>(&self, mut dc: DrawContext<B>) {
+        let size: Point<f32> = self.bounds.size.as_point();
+        let pos = Point::new(size.x * self.align.horizontal.value(), size.y * self.align.vertical.value());
+        let text = self.truncated.map_or(&self.text[..], |len| &self.text[..len]);
+        let section = self.section(text, self.truncated.is_some(), size, self.align);
+        dc.draw_text(section.with_screen_position(pos));
+    }
+
+    #[inline]
+    fn handle_event(&mut self, _event: &Event, _ctx: EventContext) -> EventResult {
+        EventResult::Pass
+    }
+
+    #[inline]
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+}
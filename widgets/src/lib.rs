@@ -1,2 +1,10 @@
+mod button;
+pub use button::*;
 mod empty;
 pub use empty::*;
+mod label;
+pub use label::*;
+mod text_area;
+pub use text_area::*;
+mod text_input;
+pub use text_input::*;
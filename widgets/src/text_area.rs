@@ -0,0 +1,801 @@
+use crate::text_input::{next_char, next_word, prev_word, Preedit};
+use crate::TextInputStyle;
+use rtk::clipboard::ClipboardRequest;
+use rtk::draw::{Text, TextLayout, TextSection};
+use rtk::event::{Axis, ButtonState, Key, MouseButton};
+use rtk::font::FontId;
+use rtk::geometry::{Border, Point};
+use rtk::prelude::*;
+use rtk_derive::{Bounds, ObjectId, Visitable};
+use std::borrow::Cow;
+use std::ops::Range;
+
+mod gap_buffer;
+pub use gap_buffer::*;
+
+/// Number of lines scrolled by every mouse wheel step.
+const WHEEL_LINES: f64 = 3.0;
+
+/// A visual row of a line, after wrapping.
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    /// Byte position where the row starts, relative to the line start.
+    start: usize,
+    /// Horizontal offset of the char boundaries in this row.
+    carets: Vec<(usize, f32)>,
+}
+
+impl Row {
+    fn x_of(&self, col: usize) -> f32 {
+        self.carets.iter().rev().find(|c| c.0 <= col).map_or(0.0, |c| c.1)
+    }
+
+    fn hit_test(&self, x: f32) -> usize {
+        let index = self
+            .carets
+            .windows(2)
+            .position(|w| x < (w[0].1 + w[1].1) / 2.0)
+            .unwrap_or(self.carets.len().saturating_sub(1));
+        self.carets.get(index).map_or(self.start, |c| c.0)
+    }
+}
+
+/// Layout of a single line.
+#[derive(Debug, Clone, PartialEq)]
+struct LineLayout {
+    rows: Vec<Row>,
+}
+
+impl LineLayout {
+    #[inline]
+    fn row_of(&self, col: usize) -> usize {
+        self.rows.iter().rposition(|r| r.start <= col).unwrap_or(0)
+    }
+
+    /// Gets the row and horizontal offset of a position relative to the line start.
+    #[inline]
+    fn locate(&self, col: usize) -> (usize, f32) {
+        let row = self.row_of(col);
+        (row, self.rows[row].x_of(col))
+    }
+}
+
+/// Multi-line editable text area.
+///
+/// The text is stored in a `GapBuffer`. Only the lines that become visible are laid out, the ones that
+/// weren't laid out yet are assumed to take a single row when computing the scroll range. The number of rows
+/// before every line is cached, so finding the line at some position doesn't walk all the previous lines.
+#[derive(Debug, Clone, PartialEq, ObjectId, Bounds, Visitable)]
+pub struct TextArea {
+    id: WidgetId,
+    bounds: Rect,
+    buffer: GapBuffer,
+    font: FontId,
+    font_size: f32,
+    style: TextInputStyle,
+    wrap: bool,
+    read_only: bool,
+    /// Caret byte position.
+    caret: usize,
+    /// Selection anchor byte position. The selection spans from the anchor to the caret.
+    anchor: usize,
+    focused: bool,
+    dragging: bool,
+    scroll: Position,
+    /// Scroll to the caret on the next layout.
+    follow_caret: bool,
+    /// Horizontal offset kept while moving the caret vertically.
+    preferred_x: Option<f32>,
+    /// Caret move that needs a layout to be resolved: target point in content coordinates, and if it extends
+    /// the selection.
+    pending_move: Option<(Point<f32>, bool)>,
+    lines: Vec<Option<LineLayout>>,
+    /// Number of rows before every line, with the total at the end.
+    row_offsets: Vec<usize>,
+    layout_width: f32,
+    line_height: u32,
+    preedit: Option<Preedit>,
+    /// Clipboard operation to perform on the next layout.
+    clipboard_req: Option<ClipboardRequest>,
+}
+
+impl TextArea {
+    /// Creates an empty text area of the specified size.
+    pub fn new(size: impl Into<Size>) -> Self {
+        TextArea {
+            id: WidgetId::new(),
+            bounds: Rect::new_at_origin(size),
+            buffer: GapBuffer::new(),
+            font: Default::default(),
+            font_size: 16.0,
+            style: Default::default(),
+            wrap: true,
+            read_only: false,
+            caret: 0,
+            anchor: 0,
+            focused: false,
+            dragging: false,
+            scroll: Default::default(),
+            follow_caret: false,
+            preferred_x: None,
+            pending_move: None,
+            lines: vec![None],
+            row_offsets: vec![0, 1],
+            layout_width: 0.0,
+            line_height: 0,
+            preedit: None,
+            clipboard_req: None,
+        }
+    }
+
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    #[inline]
+    pub fn with_font(mut self, font: FontId) -> Self {
+        self.font = font;
+        self.clear_layout();
+        self
+    }
+
+    #[inline]
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self.clear_layout();
+        self
+    }
+
+    #[inline]
+    pub fn with_style(self, style: TextInputStyle) -> Self {
+        TextArea { style, ..self }
+    }
+
+    /// Enables or disables soft wrapping of long lines (enabled by default).
+    #[inline]
+    pub fn with_wrap(self, wrap: bool) -> Self {
+        TextArea { wrap, ..self }
+    }
+
+    /// Makes the text area read only. The text can still be selected.
+    #[inline]
+    pub fn with_read_only(self, read_only: bool) -> Self {
+        TextArea { read_only, ..self }
+    }
+
+    /// Gets the text.
+    #[inline]
+    pub fn get_text(&self) -> Cow<'_, str> {
+        self.buffer.slice(0..self.buffer.len())
+    }
+
+    /// Replaces the text, moving the caret to the start.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.buffer = GapBuffer::from(text.into());
+        self.lines = vec![None; self.buffer.line_count()];
+        self.update_row_offsets(0);
+        self.caret = 0;
+        self.anchor = 0;
+        self.scroll = Default::default();
+        self.preferred_x = None;
+        self.pending_move = None;
+    }
+
+    /// Gets the text buffer.
+    #[inline]
+    pub fn buffer(&self) -> &GapBuffer {
+        &self.buffer
+    }
+
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Gets the caret byte position.
+    #[inline]
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Gets the line and byte column of the caret.
+    #[inline]
+    pub fn caret_line_col(&self) -> (usize, usize) {
+        let line = self.buffer.line_of(self.caret);
+        (line, self.caret - self.buffer.line_range(line).start)
+    }
+
+    /// Gets the selected byte range.
+    #[inline]
+    pub fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    /// Gets the selected text.
+    #[inline]
+    pub fn selected_text(&self) -> Cow<'_, str> {
+        self.buffer.slice(self.selection())
+    }
+
+    /// Selects a byte range. The caret is placed at the end.
+    pub fn select(&mut self, range: Range<usize>) {
+        assert!(self.buffer.is_char_boundary(range.start) && self.buffer.is_char_boundary(range.end));
+        self.anchor = range.start;
+        self.move_caret(range.end, true);
+    }
+
+    /// Selects all the text.
+    #[inline]
+    pub fn select_all(&mut self) {
+        self.select(0..self.buffer.len())
+    }
+
+    /// Checks if the text area has keyboard focus.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Replaces the selection with the specified text.
+    pub fn insert(&mut self, text: &str) {
+        let sel = self.selection();
+        let (first, last) = (self.buffer.line_of(sel.start), self.buffer.line_of(sel.end));
+        self.buffer.replace(sel.clone(), text);
+        let new_lines = text.matches('\n').count() + 1;
+        self.lines.splice(first..=last, std::iter::repeat_n(None, new_lines));
+        self.update_row_offsets(first);
+        self.move_caret(sel.start + text.len(), false);
+    }
+
+    /// Copies the selection to the clipboard.
+    fn copy(&mut self) {
+        if self.caret != self.anchor {
+            self.clipboard_req = Some(ClipboardRequest::Copy(self.selected_text().into()));
+        }
+    }
+
+    /// Moves the caret, extending the selection if requested.
+    fn move_caret(&mut self, pos: usize, select: bool) {
+        self.caret = pos;
+        if !select {
+            self.anchor = pos;
+        }
+        self.preferred_x = None;
+        self.pending_move = None;
+        self.follow_caret = true;
+    }
+
+    /// Deletes the selection, or the text between the caret and the specified position.
+    fn delete_to(&mut self, pos: usize) {
+        if self.caret == self.anchor {
+            self.anchor = pos;
+        }
+        self.insert("");
+    }
+
+    fn prev_pos(&self, word: bool) -> usize {
+        let (line, col) = self.caret_line_col();
+        if !word || col == 0 {
+            return self.buffer.prev_char(self.caret);
+        }
+        self.buffer.line_range(line).start + prev_word(&self.buffer.line(line), col)
+    }
+
+    fn next_pos(&self, word: bool) -> usize {
+        let (line, col) = self.caret_line_col();
+        let range = self.buffer.line_range(line);
+        if !word || self.caret == range.end {
+            return self.buffer.next_char(self.caret);
+        }
+        range.start + next_word(&self.buffer.line(line), col)
+    }
+
+    /// Selects the word under the caret.
+    fn select_word(&mut self) {
+        let (line, col) = self.caret_line_col();
+        let text = self.buffer.line(line);
+        let start = prev_word(&text, next_char(&text, col));
+        let end = text[start..].find(char::is_whitespace).map_or(text.len(), |i| start + i);
+        let line_start = self.buffer.line_range(line).start;
+        self.select(line_start + start..line_start + end);
+    }
+
+    fn section<'a>(&self, text: &'a str) -> TextSection<'a> {
+        let layout = if self.wrap {
+            TextLayout::default_wrap()
+        } else {
+            TextLayout::default_single_line()
+        };
+        TextSection::default()
+            .add_text(
+                Text::new(text)
+                    .with_scale(self.font_size)
+                    .with_color(self.style.text)
+                    .with_font_id(self.font),
+            )
+            .with_bounds(Point::new(self.layout_width, f32::INFINITY))
+            .with_layout(layout)
+    }
+
+    #[inline]
+    fn row_count(&self, line: usize) -> usize {
+        self.lines[line].as_ref().map_or(1, |l| l.rows.len())
+    }
+
+    /// Recomputes the row offsets of the lines after `line`, when it's row count changed.
+    fn update_row_offsets(&mut self, line: usize) {
+        self.row_offsets.truncate(line + 1);
+        let mut rows = self.row_offsets[line];
+        for layout in &self.lines[line..] {
+            rows += layout.as_ref().map_or(1, |l| l.rows.len());
+            self.row_offsets.push(rows);
+        }
+    }
+
+    /// Discards the layout of all lines.
+    fn clear_layout(&mut self) {
+        self.lines.iter_mut().for_each(|l| *l = None);
+        self.update_row_offsets(0);
+    }
+
+    /// Vertical offset of a line in content coordinates.
+    #[inline]
+    fn line_y(&self, line: usize) -> f32 {
+        (self.row_offsets[line] as u32 * self.line_height) as f32
+    }
+
+    /// Total height of the content, estimating one row for the lines that weren't laid out.
+    #[inline]
+    fn content_height(&self) -> u32 {
+        self.row_offsets[self.lines.len()] as u32 * self.line_height
+    }
+
+    /// Finds the line at a vertical offset, and the offset where it starts.
+    fn line_at(&self, y: f32) -> Option<(usize, f32)> {
+        if y < 0.0 || self.line_height == 0 {
+            return None;
+        }
+        let row = (y / self.line_height as f32) as usize;
+        let line = self.row_offsets.partition_point(|&rows| rows <= row) - 1;
+        (line < self.lines.len()).then(|| (line, self.line_y(line)))
+    }
+
+    /// Finds the byte position closest to a point in content coordinates.
+    fn pos_at(&self, point: Point<f32>) -> usize {
+        let (line, line_y) = match self.line_at(point.y) {
+            Some(found) => found,
+            None if point.y < 0.0 => return 0,
+            None => return self.buffer.len(),
+        };
+        let start = self.buffer.line_range(line).start;
+        match &self.lines[line] {
+            Some(layout) => {
+                let row = (((point.y - line_y) / self.line_height as f32) as usize).min(layout.rows.len() - 1);
+                start + layout.rows[row].hit_test(point.x)
+            }
+            None => start,
+        }
+    }
+
+    /// Gets the position of the caret in content coordinates, if it's line was laid out.
+    fn caret_point(&self) -> Option<Point<f32>> {
+        let (line, col) = self.caret_line_col();
+        let (row, x) = self.lines[line].as_ref()?.locate(col);
+        Some(Point::new(x, self.line_y(line) + (row as u32 * self.line_height) as f32))
+    }
+
+    /// Gets the position where the caret is drawn, inside the composition text if there is one.
+    fn caret_point_ime(&self) -> Option<Point<f32>> {
+        let caret = self.caret_point()?;
+        let dx = self.preedit.as_ref().and_then(|p| p.cursor_x).unwrap_or(0.0);
+        Some(Point::new(caret.x + dx, caret.y))
+    }
+
+    #[inline]
+    fn preedit_section<'a>(&self, text: &'a str) -> TextSection<'a> {
+        self.section(text).with_layout(TextLayout::default_single_line())
+    }
+
+    #[inline]
+    fn content_size(&self) -> Size {
+        self.bounds.size.remove_border(self.style.padding)
+    }
+
+    /// Point in content coordinates from a position relative to the widget.
+    #[inline]
+    fn content_point(&self, local_pos: Point<f64>) -> Point<f32> {
+        let padding = self.style.padding;
+        Point::new(
+            local_pos.x as f32 - padding.left as f32 + self.scroll.x as f32,
+            local_pos.y as f32 - padding.top as f32 + self.scroll.y as f32,
+        )
+    }
+
+    /// Moves the caret vertically by the specified offset. The move is completed on the next layout.
+    fn move_vertical(&mut self, dy: f32, select: bool) {
+        let caret = self.caret_point().unwrap_or_else(|| {
+            let line = self.buffer.line_of(self.caret);
+            Point::new(0.0, self.line_y(line))
+        });
+        let x = *self.preferred_x.get_or_insert(caret.x);
+        let y = caret.y + dy + self.line_height as f32 / 2.0;
+        self.pending_move = Some((Point::new(x, y), select));
+    }
+
+    fn layout_line<R: Resources>(&mut self, line: usize, resources: &mut R) -> bool {
+        if self.lines[line].is_some() {
+            return false;
+        }
+        let text = self.buffer.line(line);
+        let glyphs = resources.glyph_positions(&self.section(&text));
+        let mut rows = vec![Row { start: 0, carets: vec![] }];
+        let mut last: Option<(f32, usize, f32)> = None; // row y, glyph end, glyph end_x
+        for glyph in &glyphs {
+            let end = glyph.byte_index + text[glyph.byte_index..].chars().next().map_or(0, char::len_utf8);
+            if let Some((y, prev_end, prev_x)) = last {
+                if glyph.pos.y > y + 0.5 {
+                    let row = rows.last_mut().unwrap();
+                    row.carets.push((prev_end, prev_x));
+                    rows.push(Row {
+                        start: glyph.byte_index,
+                        carets: vec![],
+                    });
+                }
+            }
+            rows.last_mut().unwrap().carets.push((glyph.byte_index, glyph.pos.x));
+            last = Some((glyph.pos.y, end, glyph.end_x()));
+        }
+        let row = rows.last_mut().unwrap();
+        match last {
+            Some((_, end, x)) => row.carets.push((end, x)),
+            None => row.carets.push((0, 0.0)),
+        }
+        self.lines[line] = Some(LineLayout { rows });
+        true
+    }
+
+    /// Lays out a line and updates the row offsets. Returns `true` if the line wasn't laid out before.
+    fn layout_line_rows<R: Resources>(&mut self, line: usize, resources: &mut R) -> bool {
+        let rows = self.row_count(line);
+        let laid_out = self.layout_line(line, resources);
+        if self.row_count(line) != rows {
+            self.update_row_offsets(line);
+        }
+        laid_out
+    }
+
+    /// Lays out the lines that are visible. Returns `true` if any line was laid out.
+    fn layout_visible<R: Resources>(&mut self, resources: &mut R) -> bool {
+        let top = self.scroll.y as f32;
+        let bottom = top + self.content_size().h as f32;
+        let (first, mut y) = match self.line_at(top) {
+            Some(found) => found,
+            None => return false,
+        };
+        let mut laid_out = false;
+        // the offsets are updated once, from the first line that changed it's row count
+        let mut changed = None;
+        for line in first..self.lines.len() {
+            if y >= bottom {
+                break;
+            }
+            let rows = self.row_count(line);
+            if self.layout_line(line, resources) {
+                laid_out = true;
+                if self.row_count(line) != rows {
+                    changed.get_or_insert(line);
+                }
+            }
+            y += (self.row_count(line) as u32 * self.line_height) as f32;
+        }
+        if let Some(line) = changed {
+            self.update_row_offsets(line);
+        }
+        laid_out
+    }
+
+    /// Lays out the line at a vertical offset.
+    fn layout_at<R: Resources>(&mut self, y: f32, resources: &mut R) {
+        while let Some((line, _)) = self.line_at(y) {
+            if !self.layout_line_rows(line, resources) {
+                break;
+            }
+        }
+    }
+
+    /// Adjusts the scroll to show the caret.
+    fn scroll_to_caret(&mut self) {
+        let caret = match self.caret_point() {
+            Some(p) => p,
+            None => return,
+        };
+        let visible = self.content_size();
+        let (x, y) = (caret.x.round() as i32, caret.y.round() as i32);
+        let line_height = self.line_height as i32;
+        if y + line_height > self.scroll.y + visible.h as i32 {
+            self.scroll.y = y + line_height - visible.h as i32;
+        }
+        if y < self.scroll.y {
+            self.scroll.y = y;
+        }
+        if !self.wrap {
+            if x - self.scroll.x >= visible.w as i32 {
+                self.scroll.x = x - visible.w as i32 + 1;
+            }
+            if x < self.scroll.x {
+                self.scroll.x = x;
+            }
+        }
+    }
+
+    fn clamp_scroll(&mut self) {
+        let visible = self.content_size();
+        let max_y = self.content_height().saturating_sub(visible.h) as i32;
+        self.scroll.y = self.scroll.y.min(max_y).max(0);
+        if self.wrap {
+            self.scroll.x = 0;
+        } else {
+            self.scroll.x = self.scroll.x.max(0);
+        }
+    }
+
+    fn handle_key(&mut self, key: Key, ctx: &EventContext) -> EventResult {
+        let mods = ctx.mod_state;
+        let page = self.content_size().h.saturating_sub(self.line_height) as f32;
+        match key {
+            Key::Left => {
+                let sel = self.selection();
+                let pos = if sel.is_empty() || mods.shift {
+                    self.prev_pos(mods.ctrl)
+                } else {
+                    sel.start
+                };
+                self.move_caret(pos, mods.shift);
+            }
+            Key::Right => {
+                let sel = self.selection();
+                let pos = if sel.is_empty() || mods.shift {
+                    self.next_pos(mods.ctrl)
+                } else {
+                    sel.end
+                };
+                self.move_caret(pos, mods.shift);
+            }
+            Key::Up => self.move_vertical(-(self.line_height as f32), mods.shift),
+            Key::Down => self.move_vertical(self.line_height as f32, mods.shift),
+            Key::PageUp => {
+                self.scroll.y -= page as i32;
+                self.move_vertical(-page, mods.shift);
+            }
+            Key::PageDown => {
+                self.scroll.y += page as i32;
+                self.move_vertical(page, mods.shift);
+            }
+            Key::Home if mods.ctrl => self.move_caret(0, mods.shift),
+            Key::End if mods.ctrl => self.move_caret(self.buffer.len(), mods.shift),
+            Key::Home => {
+                let line = self.buffer.line_of(self.caret);
+                self.move_caret(self.buffer.line_range(line).start, mods.shift);
+            }
+            Key::End => {
+                let line = self.buffer.line_of(self.caret);
+                self.move_caret(self.buffer.line_range(line).end, mods.shift);
+            }
+            Key::Letter('a') if mods.ctrl => self.select_all(),
+            Key::Letter('c') if mods.ctrl => self.copy(),
+            _ if self.read_only => return EventResult::Pass,
+            Key::Letter('x') if mods.ctrl => {
+                self.copy();
+                self.insert("");
+            }
+            Key::Letter('v') if mods.ctrl => self.clipboard_req = Some(ClipboardRequest::Paste),
+            Key::BackSpace => self.delete_to(self.prev_pos(mods.ctrl)),
+            Key::Delete | Key::NumpadDelete => self.delete_to(self.next_pos(mods.ctrl)),
+            Key::Enter(_) | Key::NumpadEnter => self.insert("\n"),
+            _ => return EventResult::Pass,
+        }
+        EventResult::Consumed
+    }
+}
+
+impl Widget for TextArea {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, resources: &mut R) {
+        if let Some(text) = self.clipboard_req.take().and_then(|req| req.apply(resources)) {
+            self.insert(&text.replace("\r\n", "\n").replace('\r', "\n"));
+        }
+
+        let line_height = resources
+            .measure_text(&self.section(" "))
+            .map_or(self.font_size.ceil() as u32, |rect| rect.size.h);
+        let width = if self.wrap { self.content_size().w as f32 } else { f32::INFINITY };
+        if width != self.layout_width || line_height != self.line_height {
+            self.layout_width = width;
+            self.line_height = line_height;
+            self.clear_layout();
+        }
+
+        if let Some(mut preedit) = self.preedit.take() {
+            preedit.layout(self.preedit_section(&preedit.text.clone()), resources);
+            self.preedit = Some(preedit);
+        }
+
+        self.clamp_scroll();
+        self.layout_visible(resources);
+        if let Some((point, select)) = self.pending_move.take() {
+            self.layout_at(point.y, resources);
+            let preferred_x = self.preferred_x;
+            self.move_caret(self.pos_at(point), select);
+            self.preferred_x = preferred_x;
+        }
+        if std::mem::take(&mut self.follow_caret) {
+            // laying out the visible lines can move the caret, so repeat until it settles
+            loop {
+                let line = self.buffer.line_of(self.caret);
+                self.layout_line_rows(line, resources);
+                self.scroll_to_caret();
+                self.clamp_scroll();
+                if !self.layout_visible(resources) {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn arrange<R: Resources>(&mut self, rect: Rect, resources: &mut R) {
+        self.bounds.pos = rect.pos;
+        self.update_layout(rect, resources);
+    }
+
+    fn draw<B: DrawBackend>(&self, mut dc: DrawContext<B>) {
+        let style = &self.style;
+        let padding = style.padding;
+        let rect = Rect::new(dc.origin(), self.bounds.size);
+        dc.draw_rect(rect, style.background);
+        if self.focused {
+            Border::all(1).calc_rects(rect, |r| dc.draw_rect(r, style.focus));
+        }
+
+        let origin = Point::new(padding.left as f32, padding.top as f32);
+        let top = self.scroll.y as f32;
+        let bottom = top + self.content_size().h as f32;
+        let sel = self.selection();
+        let (first, mut y) = self.line_at(top).unwrap_or((self.lines.len(), 0.0));
+        for (line, layout) in self.lines.iter().enumerate().skip(first) {
+            let h = (self.row_count(line) as u32 * self.line_height) as f32;
+            if y >= bottom {
+                break;
+            }
+            if let Some(layout) = layout {
+                let range = self.buffer.line_range(line);
+                if sel.start <= range.end && sel.end > range.start {
+                    // the line break is shown as a space when selected
+                    let extra = if sel.end > range.end { self.font_size / 4.0 } else { 0.0 };
+                    for (i, row) in layout.rows.iter().enumerate() {
+                        let row_end = layout.rows.get(i + 1).map_or(range.len(), |r| r.start);
+                        let start = sel.start.saturating_sub(range.start).max(row.start);
+                        let end = (sel.end - range.start).min(row_end);
+                        let x0 = row.x_of(start);
+                        let mut x1 = row.x_of(end);
+                        if i + 1 == layout.rows.len() {
+                            x1 += extra;
+                        }
+                        if start > end || x1 <= x0 {
+                            continue;
+                        }
+                        let row_y = y + (i as u32 * self.line_height) as f32;
+                        let pos = Position::new((origin.x + x0).round() as i32, (origin.y + row_y).round() as i32);
+                        let size = Size::new((x1 - x0).round().max(0.0) as u32, self.line_height);
+                        dc.draw_rect(Rect::new(pos, size), style.selection);
+                    }
+                }
+                let text = self.buffer.line(line);
+                dc.draw_text(self.section(&text).with_screen_position(Point::new(origin.x, origin.y + y)));
+            }
+            y += h;
+        }
+
+        let caret = self.caret_point();
+        if let (Some(preedit), Some(caret)) = (&self.preedit, caret) {
+            let section = self.preedit_section(&preedit.text);
+            preedit.draw(&mut dc, section, origin + caret, self.line_height, style);
+        }
+
+        if self.focused && self.preedit.as_ref().is_none_or(|p| p.cursor.is_some()) {
+            if let Some(caret) = self.caret_point_ime() {
+                let pos = Position::new((origin.x + caret.x).round() as i32, (origin.y + caret.y).round() as i32);
+                dc.draw_rect(Rect::new(pos, [1, self.line_height]), style.caret);
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        match event {
+            Event::Keyboard {
+                state: ButtonState::Pressed,
+                key,
+                ..
+            } if self.focused => self.handle_key(*key, &ctx),
+            // AltGr is reported as ctrl+alt, only plain ctrl combinations are shortcuts
+            Event::Character(c) if self.focused && !c.is_control() && (!ctx.mod_state.ctrl || ctx.mod_state.alt) && !self.read_only => {
+                let mut buf = [0; 4];
+                self.insert(c.encode_utf8(&mut buf));
+                EventResult::Consumed
+            }
+            Event::ImePreedit { text, cursor } if self.focused && !self.read_only => {
+                self.preedit = if text.is_empty() {
+                    None
+                } else {
+                    // the composition replaces the selection
+                    self.insert("");
+                    Some(Preedit::new(text.clone(), *cursor))
+                };
+                EventResult::Consumed
+            }
+            Event::ImeCommit(text) if self.focused && !self.read_only => {
+                self.preedit = None;
+                self.insert(text);
+                EventResult::Consumed
+            }
+            Event::MouseButton(ButtonState::Pressed, MouseButton::Left) => {
+                let pos = self.pos_at(self.content_point(ctx.local_pos));
+                self.move_caret(pos, ctx.mod_state.shift);
+                self.dragging = true;
+                EventResult::ConsumedFocus
+            }
+            Event::MouseButton(ButtonState::Released, MouseButton::Left) => {
+                self.dragging = false;
+                EventResult::Consumed
+            }
+            Event::MouseMoved(Axis::Position(_)) if self.dragging => {
+                let pos = self.pos_at(self.content_point(ctx.local_pos));
+                self.move_caret(pos, true);
+                EventResult::Consumed
+            }
+            Event::MouseMoved(Axis::Scroll(delta, _)) => {
+                let delta = delta.to_pixels(WHEEL_LINES * self.line_height as f64);
+                self.scroll.y -= delta.y.round() as i32;
+                if !self.wrap {
+                    self.scroll.x -= delta.x.round() as i32;
+                }
+                EventResult::Consumed
+            }
+            Event::DoubleClick(MouseButton::Left) => {
+                self.select_word();
+                EventResult::Consumed
+            }
+            Event::FocusGained => {
+                self.focused = true;
+                EventResult::Consumed
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.dragging = false;
+                self.preedit = None;
+                EventResult::Consumed
+            }
+            _ => EventResult::Pass,
+        }
+    }
+
+    #[inline]
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+
+    #[inline]
+    fn viewport_origin(&self) -> Position {
+        self.scroll
+    }
+
+    #[inline]
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        let padding = self.style.padding;
+        let caret = self.caret_point_ime()?;
+        let x = padding.left as i32 + caret.x.round() as i32 - self.scroll.x;
+        let y = padding.top as i32 + caret.y.round() as i32 - self.scroll.y;
+        Some(Rect::new([x, y], [1, self.line_height]))
+    }
+}
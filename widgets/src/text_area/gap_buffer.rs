@@ -0,0 +1,311 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+const MIN_GAP: usize = 64;
+
+/// Text buffer with a movable gap at the edit position.
+///
+/// Consecutive edits around the same position are cheap, since only the gap moves. The buffer also keeps
+/// track of line starts, so lines can be accessed without scanning the text.
+///
+/// All positions are byte offsets and must be at char boundaries.
+#[derive(Clone)]
+pub struct GapBuffer {
+    buf: Vec<u8>,
+    gap: Range<usize>,
+    /// Byte position where every line starts. The first line always starts at 0.
+    line_starts: Vec<usize>,
+}
+
+impl GapBuffer {
+    /// Creates an empty buffer.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from("")
+    }
+
+    /// Length of the text in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.gap.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if a byte position is at a char boundary.
+    pub fn is_char_boundary(&self, pos: usize) -> bool {
+        pos == self.len() || (pos < self.len() && self.byte(pos) & 0xC0 != 0x80)
+    }
+
+    /// Gets the char boundary before a byte position.
+    pub fn prev_char(&self, pos: usize) -> usize {
+        let mut pos = pos.min(self.len());
+        while pos > 0 {
+            pos -= 1;
+            if self.is_char_boundary(pos) {
+                break;
+            }
+        }
+        pos
+    }
+
+    /// Gets the char boundary after a byte position.
+    pub fn next_char(&self, pos: usize) -> usize {
+        let len = self.len();
+        let mut pos = pos;
+        while pos < len {
+            pos += 1;
+            if self.is_char_boundary(pos) {
+                break;
+            }
+        }
+        pos.min(len)
+    }
+
+    /// Gets a range of text. Only allocates if the range crosses the gap.
+    pub fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        self.check_range(&range);
+        let gap_len = self.gap.len();
+        if range.end <= self.gap.start {
+            Cow::Borrowed(as_str(&self.buf[range]))
+        } else if range.start >= self.gap.start {
+            Cow::Borrowed(as_str(&self.buf[range.start + gap_len..range.end + gap_len]))
+        } else {
+            let mut s = String::with_capacity(range.len());
+            s.push_str(as_str(&self.buf[range.start..self.gap.start]));
+            s.push_str(as_str(&self.buf[self.gap.end..range.end + gap_len]));
+            Cow::Owned(s)
+        }
+    }
+
+    /// Inserts text at a byte position.
+    pub fn insert(&mut self, pos: usize, text: &str) {
+        self.replace(pos..pos, text)
+    }
+
+    /// Removes a range of text.
+    pub fn remove(&mut self, range: Range<usize>) {
+        self.replace(range, "")
+    }
+
+    /// Replaces a range of text.
+    pub fn replace(&mut self, range: Range<usize>, text: &str) {
+        self.check_range(&range);
+        self.move_gap(range.start);
+        self.gap.end += range.len();
+        if self.gap.len() < text.len() {
+            self.grow(text.len());
+        }
+        self.buf[self.gap.start..self.gap.start + text.len()].copy_from_slice(text.as_bytes());
+        self.gap.start += text.len();
+
+        // update the line starts after the edit position
+        let line = self.line_of_start(range.start);
+        let delta = text.len() as isize - range.len() as isize;
+        let new_starts = text.match_indices('\n').map(|(i, _)| range.start + i + 1);
+        let old_starts = self.line_starts.split_off(line + 1);
+        self.line_starts.extend(new_starts);
+        self.line_starts.extend(
+            old_starts
+                .into_iter()
+                .filter(|&s| s > range.end)
+                .map(|s| (s as isize + delta) as usize),
+        );
+    }
+
+    /// Number of lines in the text. An empty buffer has one line.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Gets the byte range of a line, without the line break.
+    pub fn line_range(&self, line: usize) -> Range<usize> {
+        let start = self.line_starts[line];
+        let end = self.line_starts.get(line + 1).map_or(self.len(), |&next| next - 1);
+        start..end
+    }
+
+    /// Gets the text of a line, without the line break.
+    #[inline]
+    pub fn line(&self, line: usize) -> Cow<'_, str> {
+        self.slice(self.line_range(line))
+    }
+
+    /// Finds the line that contains a byte position.
+    #[inline]
+    pub fn line_of(&self, pos: usize) -> usize {
+        self.line_of_start(pos.min(self.len()))
+    }
+
+    #[inline]
+    fn line_of_start(&self, pos: usize) -> usize {
+        self.line_starts.partition_point(|&s| s <= pos) - 1
+    }
+
+    #[inline]
+    fn byte(&self, pos: usize) -> u8 {
+        self.buf[self.buf_pos(pos)]
+    }
+
+    /// Converts a text position into a position inside the buffer.
+    #[inline]
+    fn buf_pos(&self, pos: usize) -> usize {
+        if pos < self.gap.start {
+            pos
+        } else {
+            pos + self.gap.len()
+        }
+    }
+
+    fn check_range(&self, range: &Range<usize>) {
+        assert!(
+            range.start <= range.end && self.is_char_boundary(range.start) && self.is_char_boundary(range.end),
+            "invalid range {:?}",
+            range
+        );
+    }
+
+    fn move_gap(&mut self, pos: usize) {
+        let gap_len = self.gap.len();
+        if pos < self.gap.start {
+            self.buf.copy_within(pos..self.gap.start, pos + gap_len);
+        } else if pos > self.gap.start {
+            self.buf.copy_within(self.gap.end..pos + gap_len, self.gap.start);
+        }
+        self.gap = pos..pos + gap_len;
+    }
+
+    fn grow(&mut self, min_gap: usize) {
+        let extra = min_gap.max(self.buf.len() / 2).max(MIN_GAP) - self.gap.len();
+        let tail = self.buf.len() - self.gap.end;
+        self.buf.resize(self.buf.len() + extra, 0);
+        let new_end = self.buf.len() - tail;
+        self.buf.copy_within(self.gap.end..self.gap.end + tail, new_end);
+        self.gap.end = new_end;
+    }
+}
+
+impl Default for GapBuffer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for GapBuffer {
+    fn from(text: &str) -> Self {
+        let mut buffer = GapBuffer {
+            buf: vec![],
+            gap: 0..0,
+            line_starts: vec![0],
+        };
+        buffer.insert(0, text);
+        buffer
+    }
+}
+
+impl From<String> for GapBuffer {
+    #[inline]
+    fn from(text: String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+impl fmt::Display for GapBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(as_str(&self.buf[..self.gap.start]))?;
+        f.write_str(as_str(&self.buf[self.gap.end..]))
+    }
+}
+
+impl fmt::Debug for GapBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), f)
+    }
+}
+
+impl PartialEq for GapBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.to_string() == other.to_string()
+    }
+}
+
+#[inline]
+fn as_str(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).expect("gap buffer split a char")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_moves_gap() {
+        let mut buf = GapBuffer::from("hello world");
+        // the gap stays after the last edit
+        assert_eq!(buf.gap.start, 11);
+        buf.replace(0..5, "goodbye");
+        assert_eq!(buf.to_string(), "goodbye world");
+        assert_eq!(buf.gap.start, 7);
+        buf.replace(8..13, "moon");
+        assert_eq!(buf.to_string(), "goodbye moon");
+        assert_eq!(buf.gap.start, 12);
+        buf.replace(0..12, "");
+        assert!(buf.is_empty());
+        assert_eq!(buf.gap, 0..buf.buf.len());
+    }
+
+    #[test]
+    fn move_gap_keeps_text() {
+        let mut buf = GapBuffer::from("abcdef");
+        let gap_len = buf.gap.len();
+        for &pos in &[0, 3, 6, 2, 5, 0] {
+            buf.move_gap(pos);
+            assert_eq!(buf.gap, pos..pos + gap_len);
+            assert_eq!(buf.to_string(), "abcdef");
+            assert_eq!(buf.slice(1..5), "bcde");
+        }
+    }
+
+    #[test]
+    fn growth() {
+        let mut buf = GapBuffer::new();
+        assert_eq!(buf.buf.len(), 0);
+        buf.insert(0, "ab");
+        assert_eq!(buf.buf.len(), MIN_GAP);
+        assert_eq!(buf.gap.len(), MIN_GAP - 2);
+
+        // the text after the gap is moved to the end of the new buffer
+        buf.move_gap(1);
+        let text = "x".repeat(MIN_GAP);
+        buf.insert(1, &text);
+        assert_eq!(buf.len(), MIN_GAP + 2);
+        assert!(buf.buf.len() >= buf.len());
+        assert_eq!(buf.buf[buf.buf.len() - 1], b'b');
+        assert_eq!(buf.to_string(), format!("a{}b", text));
+    }
+
+    #[test]
+    fn line_starts_after_edits() {
+        let mut buf = GapBuffer::from("one\ntwo\nthree");
+        assert_eq!(buf.line_starts, vec![0, 4, 8]);
+        // join the first two lines
+        buf.remove(3..4);
+        assert_eq!(buf.line_starts, vec![0, 7]);
+        // split a line twice
+        buf.insert(2, "\n\n");
+        assert_eq!(buf.line_starts, vec![0, 3, 4, 9]);
+        // replace across line breaks
+        buf.replace(1..5, "X\nY");
+        assert_eq!(buf.to_string(), "oX\nYtwo\nthree");
+        assert_eq!(buf.line_starts, vec![0, 3, 8]);
+        buf.insert(buf.len(), "\n");
+        assert_eq!(buf.line_starts, vec![0, 3, 8, 14]);
+        assert_eq!(buf.line(3), "");
+    }
+}
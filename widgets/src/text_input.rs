@@ -0,0 +1,569 @@
+use rtk::clipboard::ClipboardRequest;
+use rtk::draw::{Text, TextLayout, TextSection};
+use rtk::event::{Axis, ButtonState, Key, MouseButton};
+use rtk::font::FontId;
+use rtk::geometry::{Border, Point, SizeHint};
+use rtk::prelude::*;
+use rtk_derive::{Bounds, ObjectId, Visitable};
+use std::ops::Range;
+
+/// Colors and spacing used to draw a text input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextInputStyle {
+    pub background: Color,
+    pub text: Color,
+    pub selection: Color,
+    pub caret: Color,
+    /// Border color while the input has keyboard focus.
+    pub focus: Color,
+    /// Space between the input edges and the text.
+    pub padding: Border,
+}
+
+impl Default for TextInputStyle {
+    fn default() -> Self {
+        TextInputStyle {
+            background: Color::WHITE,
+            text: Color::BLACK,
+            selection: Color::rgb(0.5, 0.7, 1.0),
+            caret: Color::BLACK,
+            focus: Color::BLUE,
+            padding: Border::all(3),
+        }
+    }
+}
+
+/// Single line editable text field.
+///
+/// Pressing Enter returns `ConsumedNotifyTarget` with the parent as target, so the parent can
+/// handle the submission on `Widget::event_consumed`.
+#[derive(Debug, Clone, PartialEq, ObjectId, Bounds, Visitable)]
+pub struct TextInput {
+    id: WidgetId,
+    bounds: Rect,
+    /// Preferred width, used when measured.
+    width: u32,
+    text: String,
+    font: FontId,
+    font_size: f32,
+    style: TextInputStyle,
+    password: Option<char>,
+    /// Caret byte position.
+    caret: usize,
+    /// Selection anchor byte position. The selection spans from the anchor to the caret.
+    anchor: usize,
+    focused: bool,
+    dragging: bool,
+    /// Horizontal scroll offset.
+    scroll: i32,
+    /// Horizontal offset of every char boundary, computed during layout.
+    offsets: Vec<f32>,
+    line_height: u32,
+    preedit: Option<Preedit>,
+    /// Clipboard operation to perform on the next layout.
+    clipboard_req: Option<ClipboardRequest>,
+}
+
+impl TextInput {
+    /// Creates an empty text input of the specified width.
+    ///
+    /// The width is reported as the preferred size, but the input takes the width it's arranged with.
+    pub fn new(width: u32) -> Self {
+        TextInput {
+            id: WidgetId::new(),
+            bounds: Rect::new_at_origin([width, 0]),
+            width,
+            text: String::new(),
+            font: Default::default(),
+            font_size: 16.0,
+            style: Default::default(),
+            password: None,
+            caret: 0,
+            anchor: 0,
+            focused: false,
+            dragging: false,
+            scroll: 0,
+            offsets: vec![0.0],
+            line_height: 0,
+            preedit: None,
+            clipboard_req: None,
+        }
+    }
+
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    #[inline]
+    pub fn with_font(self, font: FontId) -> Self {
+        TextInput { font, ..self }
+    }
+
+    #[inline]
+    pub fn with_font_size(self, font_size: f32) -> Self {
+        TextInput { font_size, ..self }
+    }
+
+    #[inline]
+    pub fn with_style(self, style: TextInputStyle) -> Self {
+        TextInput { style, ..self }
+    }
+
+    /// Hides the text, showing the mask character instead of every char.
+    #[inline]
+    pub fn with_password(self, mask: char) -> Self {
+        TextInput {
+            password: Some(mask),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text, moving the caret to the end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.caret = self.text.len();
+        self.anchor = self.caret;
+    }
+
+    /// Gets the caret byte position.
+    #[inline]
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Gets the selected byte range.
+    #[inline]
+    pub fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    /// Gets the selected text.
+    #[inline]
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection()]
+    }
+
+    /// Selects a byte range. The caret is placed at the end.
+    pub fn select(&mut self, range: Range<usize>) {
+        assert!(self.text.is_char_boundary(range.start) && self.text.is_char_boundary(range.end));
+        self.anchor = range.start;
+        self.caret = range.end;
+    }
+
+    /// Selects all the text.
+    #[inline]
+    pub fn select_all(&mut self) {
+        self.select(0..self.text.len())
+    }
+
+    /// Checks if the input has keyboard focus.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Replaces the selection with the specified text.
+    pub fn insert(&mut self, text: &str) {
+        let sel = self.selection();
+        self.text.replace_range(sel.clone(), text);
+        self.caret = sel.start + text.len();
+        self.anchor = self.caret;
+    }
+
+    /// Copies the selection to the clipboard. Password inputs never copy their text.
+    fn copy(&mut self) {
+        if self.password.is_none() && self.caret != self.anchor {
+            self.clipboard_req = Some(ClipboardRequest::Copy(self.selected_text().into()));
+        }
+    }
+
+    /// Moves the caret, extending the selection if requested.
+    #[inline]
+    fn move_caret(&mut self, pos: usize, select: bool) {
+        self.caret = pos;
+        if !select {
+            self.anchor = pos;
+        }
+    }
+
+    /// Deletes the selection, or the text between the caret and the specified position.
+    fn delete_to(&mut self, pos: usize) {
+        if self.caret == self.anchor {
+            self.anchor = pos;
+        }
+        self.insert("");
+    }
+
+    fn prev_pos(&self, word: bool) -> usize {
+        match (word, self.password) {
+            (false, _) => prev_char(&self.text, self.caret),
+            (true, None) => prev_word(&self.text, self.caret),
+            (true, Some(_)) => 0,
+        }
+    }
+
+    fn next_pos(&self, word: bool) -> usize {
+        match (word, self.password) {
+            (false, _) => next_char(&self.text, self.caret),
+            (true, None) => next_word(&self.text, self.caret),
+            (true, Some(_)) => self.text.len(),
+        }
+    }
+
+    /// The text that is displayed, masked if this is a password input.
+    fn display_text(&self) -> String {
+        match self.password {
+            Some(mask) => self.text.chars().map(|_| mask).collect(),
+            None => self.text.clone(),
+        }
+    }
+
+    fn section<'a>(&self, text: &'a str) -> TextSection<'a> {
+        TextSection::default()
+            .add_text(
+                Text::new(text)
+                    .with_scale(self.font_size)
+                    .with_color(self.style.text)
+                    .with_font_id(self.font),
+            )
+            .with_layout(TextLayout::default_single_line())
+    }
+
+    /// Gets the height of a line of text.
+    fn measure_line_height<R: Resources>(&self, resources: &mut R) -> u32 {
+        resources
+            .measure_text(&self.section(" "))
+            .map_or(self.font_size.ceil() as u32, |rect| rect.size.h)
+    }
+
+    /// Gets the horizontal offset of a byte position.
+    fn offset_of(&self, pos: usize) -> f32 {
+        let index = self.text[..pos].chars().count();
+        self.offsets.get(index).or_else(|| self.offsets.last()).copied().unwrap_or(0.0)
+    }
+
+    /// Gets the horizontal offset where the caret is drawn, inside the composition text if there is one.
+    fn caret_x(&self) -> f32 {
+        let x = self.offset_of(self.caret);
+        self.preedit.as_ref().map_or(x, |p| x + p.cursor_x.unwrap_or(0.0))
+    }
+
+    /// Finds the byte position closest to a point relative to the widget.
+    fn hit_test(&self, local_pos: Point<f64>) -> usize {
+        let x = (local_pos.x as f32) - self.style.padding.left as f32 + self.scroll as f32;
+        let index = self
+            .offsets
+            .windows(2)
+            .position(|w| x < (w[0] + w[1]) / 2.0)
+            .unwrap_or(self.offsets.len() - 1);
+        self.text.char_indices().nth(index).map_or(self.text.len(), |(i, _)| i)
+    }
+
+    fn handle_key(&mut self, key: Key, ctx: &EventContext) -> EventResult {
+        let mods = ctx.mod_state;
+        match key {
+            Key::Left => {
+                let sel = self.selection();
+                let pos = if sel.is_empty() || mods.shift {
+                    self.prev_pos(mods.ctrl)
+                } else {
+                    sel.start
+                };
+                self.move_caret(pos, mods.shift);
+            }
+            Key::Right => {
+                let sel = self.selection();
+                let pos = if sel.is_empty() || mods.shift {
+                    self.next_pos(mods.ctrl)
+                } else {
+                    sel.end
+                };
+                self.move_caret(pos, mods.shift);
+            }
+            Key::Home => self.move_caret(0, mods.shift),
+            Key::End => self.move_caret(self.text.len(), mods.shift),
+            Key::BackSpace => self.delete_to(self.prev_pos(mods.ctrl)),
+            Key::Delete | Key::NumpadDelete => self.delete_to(self.next_pos(mods.ctrl)),
+            Key::Letter('a') if mods.ctrl => self.select_all(),
+            Key::Letter('c') if mods.ctrl => self.copy(),
+            Key::Letter('x') if mods.ctrl => {
+                self.copy();
+                self.insert("");
+            }
+            Key::Letter('v') if mods.ctrl => self.clipboard_req = Some(ClipboardRequest::Paste),
+            Key::Enter(_) | Key::NumpadEnter => return EventResult::ConsumedNotifyTarget(ctx.parent),
+            _ => return EventResult::Pass,
+        }
+        EventResult::Consumed
+    }
+}
+
+impl Widget for TextInput {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, resources: &mut R) {
+        if let Some(text) = self.clipboard_req.take().and_then(|req| req.apply(resources)) {
+            self.insert(&single_line(&text));
+        }
+
+        let padding = self.style.padding;
+        let display = self.display_text();
+        let glyphs = resources.glyph_positions(&self.section(&display));
+
+        // caret offsets at every char boundary
+        let mut offsets = vec![None; display.chars().count() + 1];
+        let char_index = |byte_index| display[..byte_index].chars().count();
+        for glyph in &glyphs {
+            offsets[char_index(glyph.byte_index)] = Some(glyph.pos.x);
+        }
+        let end = glyphs.iter().map(|g| g.end_x()).fold(0.0, f32::max);
+        *offsets.last_mut().unwrap() = Some(end);
+        let mut prev = 0.0;
+        self.offsets = offsets
+            .into_iter()
+            .map(|x| {
+                prev = x.unwrap_or(prev);
+                prev
+            })
+            .collect();
+
+        self.line_height = self.measure_line_height(resources);
+        self.bounds.size.h = self.line_height + padding.total_height();
+
+        // scroll to keep the caret visible
+        let visible = self.bounds.size.w.saturating_sub(padding.total_width()) as i32;
+        if let Some(mut preedit) = self.preedit.take() {
+            preedit.layout(self.section(&preedit.text.clone()), resources);
+            self.preedit = Some(preedit);
+        }
+        let caret_x = self.caret_x().round() as i32;
+        let max_scroll = (end.ceil() as i32 + 1 - visible).max(0);
+        if caret_x - self.scroll >= visible {
+            self.scroll = caret_x - visible + 1;
+        }
+        if caret_x < self.scroll {
+            self.scroll = caret_x;
+        }
+        self.scroll = self.scroll.min(max_scroll).max(0);
+    }
+
+    fn measure<R: Resources>(&mut self, _avail: Size, resources: &mut R) -> SizeHint {
+        let padding = self.style.padding;
+        let height = self.measure_line_height(resources) + padding.total_height();
+        // the text scrolls, so any width works
+        SizeHint::new([padding.total_width(), height], [self.width, height], [Size::unbounded().w, height])
+    }
+
+    fn arrange<R: Resources>(&mut self, rect: Rect, resources: &mut R) {
+        self.bounds.pos = rect.pos;
+        self.bounds.size.w = rect.size.w;
+        self.update_layout(rect, resources);
+    }
+
+    fn draw<B: DrawBackend>(&self, mut dc: DrawContext<B>) {
+        let style = &self.style;
+        let padding = style.padding;
+        let rect = Rect::new(dc.origin(), self.bounds.size);
+        dc.draw_rect(rect, style.background);
+        if self.focused {
+            Border::all(1).calc_rects(rect, |r| dc.draw_rect(r, style.focus));
+        }
+
+        let sel = self.selection();
+        if !sel.is_empty() {
+            let x0 = self.offset_of(sel.start).round() as i32;
+            let x1 = self.offset_of(sel.end).round() as i32;
+            let pos = Position::new(padding.left as i32 + x0, padding.top as i32);
+            dc.draw_rect(Rect::new(pos, [(x1 - x0) as u32, self.line_height]), style.selection);
+        }
+
+        let display = self.display_text();
+        let text_pos = Point::new(padding.left as f32, padding.top as f32);
+        dc.draw_text(self.section(&display).with_screen_position(text_pos));
+
+        let caret_pos = Point::new(padding.left as f32 + self.offset_of(self.caret), padding.top as f32);
+        if let Some(preedit) = &self.preedit {
+            preedit.draw(&mut dc, self.section(&preedit.text), caret_pos, self.line_height, style);
+        }
+
+        if self.focused && self.preedit.as_ref().is_none_or(|p| p.cursor.is_some()) {
+            let pos = Position::new(padding.left as i32 + self.caret_x().round() as i32, padding.top as i32);
+            dc.draw_rect(Rect::new(pos, [1, self.line_height]), style.caret);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: EventContext) -> EventResult {
+        match event {
+            Event::Keyboard {
+                state: ButtonState::Pressed,
+                key,
+                ..
+            } if self.focused => self.handle_key(*key, &ctx),
+            // ctrl shortcuts are handled as keys, but AltGr is reported as ctrl+alt and produces characters
+            Event::Character(c) if self.focused && !c.is_control() && (!ctx.mod_state.ctrl || ctx.mod_state.alt) => {
+                let mut buf = [0; 4];
+                self.insert(c.encode_utf8(&mut buf));
+                EventResult::Consumed
+            }
+            Event::ImePreedit { text, cursor } if self.focused => {
+                self.preedit = if text.is_empty() {
+                    None
+                } else {
+                    // the composition replaces the selection
+                    self.insert("");
+                    Some(Preedit::new(text.clone(), *cursor))
+                };
+                EventResult::Consumed
+            }
+            Event::ImeCommit(text) if self.focused => {
+                self.preedit = None;
+                self.insert(&single_line(text));
+                EventResult::Consumed
+            }
+            Event::MouseButton(ButtonState::Pressed, MouseButton::Left) => {
+                let pos = self.hit_test(ctx.local_pos);
+                self.move_caret(pos, ctx.mod_state.shift);
+                self.dragging = true;
+                EventResult::ConsumedFocus
+            }
+            Event::MouseButton(ButtonState::Released, MouseButton::Left) => {
+                self.dragging = false;
+                EventResult::Consumed
+            }
+            Event::MouseMoved(Axis::Position(_)) if self.dragging => {
+                let pos = self.hit_test(ctx.local_pos);
+                self.move_caret(pos, true);
+                EventResult::Consumed
+            }
+            Event::DoubleClick(MouseButton::Left) => {
+                if self.password.is_some() {
+                    self.select_all();
+                } else {
+                    let start = prev_word(&self.text, next_char(&self.text, self.caret));
+                    let end = self.text[start..].find(char::is_whitespace).map_or(self.text.len(), |i| start + i);
+                    self.select(start..end);
+                }
+                EventResult::Consumed
+            }
+            Event::FocusGained => {
+                self.focused = true;
+                EventResult::Consumed
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.dragging = false;
+                self.preedit = None;
+                EventResult::Consumed
+            }
+            _ => EventResult::Pass,
+        }
+    }
+
+    #[inline]
+    fn event_consumed(&mut self, _event: &Event, _ctx: &EventContext) {}
+
+    #[inline]
+    fn viewport_origin(&self) -> Position {
+        Position::new(self.scroll, 0)
+    }
+
+    #[inline]
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+
+    fn ime_cursor_area(&self) -> Option<Rect> {
+        let padding = self.style.padding;
+        let x = padding.left as i32 + self.caret_x().round() as i32 - self.scroll;
+        Some(Rect::new([x, padding.top as i32], [1, self.line_height]))
+    }
+}
+
+/// Input method composition text, drawn over the text at the caret position.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Preedit {
+    pub text: String,
+    /// Byte range of the composition cursor.
+    pub cursor: Option<(usize, usize)>,
+    /// Width of the text, computed during layout.
+    pub width: f32,
+    /// Horizontal offset of the composition cursor, computed during layout.
+    pub cursor_x: Option<f32>,
+}
+
+impl Preedit {
+    pub fn new(text: String, cursor: Option<(usize, usize)>) -> Self {
+        Preedit {
+            text,
+            cursor,
+            width: 0.0,
+            cursor_x: None,
+        }
+    }
+
+    /// Measures the text. The section must contain only the composition text on a single line.
+    pub fn layout<R: Resources>(&mut self, section: TextSection, resources: &mut R) {
+        let glyphs = resources.glyph_positions(&section);
+        self.width = glyphs.iter().map(|g| g.end_x()).fold(0.0, f32::max);
+        self.cursor_x = self
+            .cursor
+            .map(|(start, _)| glyphs.iter().find(|g| g.byte_index >= start).map_or(self.width, |g| g.pos.x));
+    }
+
+    /// Draws the text with a background and an underline. The position is the top-left of the caret.
+    pub fn draw<B: DrawBackend>(
+        &self, dc: &mut DrawContext<B>, section: TextSection, pos: Point<f32>, line_height: u32, style: &TextInputStyle,
+    ) {
+        let width = self.width.ceil() as u32;
+        let top_left = Position::new(pos.x.round() as i32, pos.y.round() as i32);
+        dc.draw_rect(Rect::new(top_left, [width, line_height]), style.background);
+        dc.draw_text(section.with_screen_position(pos));
+        let underline = top_left.offset(0, line_height as i32 - 1);
+        dc.draw_rect(Rect::new(underline, [width, 1]), style.text);
+    }
+}
+
+/// Joins the lines of a text with spaces, since line breaks can't be entered on a single line.
+fn single_line(text: &str) -> String {
+    text.split(['\r', '\n']).filter(|s| !s.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+/// Finds the char boundary before a byte position.
+pub(crate) fn prev_char(text: &str, pos: usize) -> usize {
+    text[..pos].char_indices().next_back().map_or(0, |(i, _)| i)
+}
+
+/// Finds the char boundary after a byte position.
+pub(crate) fn next_char(text: &str, pos: usize) -> usize {
+    text[pos..].chars().next().map_or(pos, |c| pos + c.len_utf8())
+}
+
+/// Finds the start of the word before a byte position.
+pub(crate) fn prev_word(text: &str, pos: usize) -> usize {
+    let mut chars = text[..pos].char_indices().rev().skip_while(|(_, c)| c.is_whitespace());
+    let mut start = match chars.next() {
+        Some((i, _)) => i,
+        None => return 0,
+    };
+    for (i, c) in chars {
+        if c.is_whitespace() {
+            break;
+        }
+        start = i;
+    }
+    start
+}
+
+/// Finds the start of the word after a byte position.
+pub(crate) fn next_word(text: &str, pos: usize) -> usize {
+    let rest = &text[pos..];
+    let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    let next = rest[word_end..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(rest.len(), |i| word_end + i);
+    pos + next
+}
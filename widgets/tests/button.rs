@@ -0,0 +1,131 @@
+use rtk::event::{Key, KeyModState, MouseButton};
+use rtk::prelude::*;
+use rtk::testing::{EventDriver, TestBackend};
+use rtk::toplevel::Window;
+use rtk_derive::{Bounds, ObjectId, Visitable, Widget};
+use rtk_widgets::{Button, ButtonStatus, Label};
+
+#[derive(Debug, ObjectId, Bounds, Visitable, Widget)]
+enum Control {
+    Button(Button),
+    Label(Label),
+}
+
+/// Container that counts the activations of it's buttons.
+#[derive(Debug, ObjectId, Bounds, Visitable)]
+struct Form {
+    id: WidgetId,
+    bounds: Rect,
+    #[visit_iter]
+    controls: Vec<Control>,
+    activated: Vec<WidgetId>,
+}
+
+impl Widget for Form {
+    fn update_layout<R: Resources>(&mut self, _parent_rect: Rect, resources: &mut R) {
+        let mut pos = Position::new(10, 10);
+        for control in &mut self.controls {
+            control.set_position(pos);
+            control.update_layout(self.bounds, resources);
+            pos.y += control.get_size().h as i32 + 10;
+        }
+    }
+
+    fn draw<B: DrawBackend>(&self, _dc: DrawContext<B>) {}
+
+    fn handle_event(&mut self, _event: &Event, _ctx: EventContext) -> EventResult {
+        EventResult::Pass
+    }
+
+    fn event_consumed(&mut self, _event: &Event, ctx: &EventContext) {
+        self.activated.push(ctx.widget);
+    }
+}
+
+fn driver(button: Button) -> (EventDriver<Window<Form>>, WidgetId) {
+    let id = button.get_id();
+    let form = Form {
+        id: WidgetId::new(),
+        bounds: Rect::new_at_origin([200, 100]),
+        controls: vec![Control::Label(Label::new("title")), Control::Button(button)],
+        activated: vec![],
+    };
+    (EventDriver::new(Window::new(form)), id)
+}
+
+fn button(driver: &EventDriver<Window<Form>>) -> &Button {
+    match &driver.window().child.controls[1] {
+        Control::Button(button) => button,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn button_layout() {
+    let (driver, _) = driver(Button::new("ok"));
+    let button = button(&driver);
+    // text is 16x16 with the test metrics, plus the default padding
+    assert_eq!(button.get_bounds(), Rect::new([10, 36], [32, 24]));
+    assert_eq!(button.get_label().get_position(), Position::new(8, 4));
+}
+
+#[test]
+fn button_measure_arrange() {
+    let mut res = TestBackend::default();
+    let mut button = Button::new("ok");
+    assert_eq!(button.measure(Size::new(100, 100), &mut res).preferred, Size::new(32, 24));
+
+    button.arrange(Rect::new([5, 5], [100, 50]), &mut res);
+    assert_eq!(button.get_bounds(), Rect::new([5, 5], [32, 24]));
+    assert_eq!(button.get_label().get_position(), Position::new(8, 4));
+}
+
+#[test]
+fn button_mouse_activation() {
+    let (mut driver, id) = driver(Button::new("ok"));
+    assert!(driver.move_to([15, 40]));
+    assert_eq!(button(&driver).status(), ButtonStatus::Hover);
+    assert!(driver.press(MouseButton::Left));
+    assert_eq!(button(&driver).status(), ButtonStatus::Pressed);
+    assert!(button(&driver).is_focused());
+    assert!(driver.window().child.activated.is_empty());
+    assert!(driver.release(MouseButton::Left));
+    assert_eq!(button(&driver).status(), ButtonStatus::Hover);
+    assert_eq!(driver.window().child.activated, vec![id]);
+
+    // moving away cancels the click
+    driver.press(MouseButton::Left);
+    driver.move_to([150, 90]);
+    driver.release(MouseButton::Left);
+    assert_eq!(driver.window().child.activated.len(), 1);
+    assert_eq!(button(&driver).status(), ButtonStatus::Normal);
+}
+
+#[test]
+fn button_keyboard_activation() {
+    let (mut driver, id) = driver(Button::new("ok"));
+    driver.window_mut().set_focus(Some(id));
+    assert!(button(&driver).is_focused());
+    assert!(driver.press_key(Key::Space, KeyModState::default()));
+    assert!(driver.press_key(Key::Enter(false), KeyModState::default()));
+    assert!(!driver.press_key(Key::Letter('a'), KeyModState::default()));
+    assert_eq!(driver.window().child.activated, vec![id, id]);
+}
+
+#[test]
+fn button_keyboard_unfocused() {
+    let (mut driver, _) = driver(Button::new("ok"));
+    // without focus the keys are sent to every widget, the button must ignore them
+    assert!(!driver.press_key(Key::Space, KeyModState::default()));
+    assert!(!driver.press_key(Key::Enter(false), KeyModState::default()));
+    assert!(driver.window().child.activated.is_empty());
+}
+
+#[test]
+fn button_disabled() {
+    let (mut driver, _) = driver(Button::new("ok").with_enabled(false));
+    assert_eq!(button(&driver).status(), ButtonStatus::Disabled);
+    driver.click_at([15, 40], MouseButton::Left);
+    assert!(!button(&driver).is_focused());
+    assert!(driver.window().child.activated.is_empty());
+}
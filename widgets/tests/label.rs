@@ -0,0 +1,88 @@
+use rtk::geometry::{HAlign, SizeHint};
+use rtk::prelude::*;
+use rtk::testing::{TestBackend, TestDrawCmd};
+use rtk::toplevel::{TopLevel, Window};
+use rtk_widgets::{Label, TextOverflow};
+
+fn layout(label: Label, parent_size: impl Into<Size>) -> Label {
+    let mut label = label;
+    label.update_layout(Rect::new_at_origin(parent_size), &mut TestBackend::default());
+    label
+}
+
+#[test]
+fn label_natural_size() {
+    // test metrics: glyphs are half the font size wide
+    let label = layout(Label::new("hello"), [100, 100]);
+    assert_eq!(label.get_size(), Size::new(40, 16));
+
+    let label = layout(Label::new("hello").with_font_size(20.0), [100, 100]);
+    assert_eq!(label.get_size(), Size::new(50, 20));
+
+    // clipped to the available space
+    let label = layout(Label::new("hello world"), [50, 10]);
+    assert_eq!(label.get_size(), Size::new(50, 10));
+    assert_eq!(label.displayed_text(), "hello world");
+}
+
+#[test]
+fn label_wrap() {
+    let label = layout(Label::new("hello world").with_overflow(TextOverflow::Wrap), [60, 100]);
+    assert_eq!(label.get_size(), Size::new(40, 32));
+}
+
+#[test]
+fn label_ellipsis() {
+    let label = layout(Label::new("hello world").with_overflow(TextOverflow::Ellipsis), [40, 100]);
+    assert_eq!(label.displayed_text(), "hell…");
+    assert_eq!(label.get_size(), Size::new(40, 16));
+
+    // no truncation when it fits
+    let label = layout(Label::new("hello").with_overflow(TextOverflow::Ellipsis), [40, 100]);
+    assert_eq!(label.displayed_text(), "hello");
+
+    let label = layout(Label::new("hello").with_overflow(TextOverflow::Ellipsis), [4, 100]);
+    assert_eq!(label.displayed_text(), "…");
+}
+
+#[test]
+fn label_draw() {
+    let label = Label::new("hi")
+        .with_fixed_size([40, 20])
+        .with_align(HAlign::Right)
+        .with_color(Color::RED);
+    let mut window = Window::new(label);
+    let mut backend = TestBackend::default();
+    window.update_layout(&mut backend);
+    window.draw(&mut backend);
+
+    let section = backend
+        .draw_cmd
+        .iter()
+        .find_map(|cmd| match cmd {
+            TestDrawCmd::Text { text, .. } => Some(text),
+            _ => None,
+        })
+        .expect("no text drawn");
+    assert_eq!(section.screen_position, (40.0, 0.0));
+    assert_eq!(section.bounds, (40.0, 20.0));
+    assert_eq!(section.text[0].text, "hi");
+    assert_eq!(section.text[0].extra.color, [1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn label_measure_arrange() {
+    let mut res = TestBackend::default();
+    let mut label = Label::new("hello");
+    let hint = label.measure(Size::new(100, 100), &mut res);
+    assert_eq!(hint, SizeHint::fixed([40, 16]));
+
+    // with an ellipsis it can shrink down to the ellipsis alone
+    let mut label = Label::new("hello world").with_overflow(TextOverflow::Ellipsis);
+    let hint = label.measure(Size::new(100, 100), &mut res);
+    assert_eq!(hint, SizeHint::new([8, 16], [88, 16], [88, 16]));
+
+    label.arrange(Rect::new([10, 20], [40, 30]), &mut res);
+    assert_eq!(label.get_bounds(), Rect::new([10, 20], [40, 16]));
+    assert_eq!(label.displayed_text(), "hell…");
+}
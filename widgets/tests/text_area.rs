@@ -0,0 +1,260 @@
+use rtk::backend::Clipboard;
+use rtk::event::{Key, KeyModState, MouseButton};
+use rtk::prelude::*;
+use rtk::testing::{EventDriver, TestBackend, TestDrawCmd};
+use rtk::toplevel::{TopLevel, Window};
+use rtk_widgets::{GapBuffer, TextArea};
+
+const NONE: KeyModState = KeyModState {
+    shift: false,
+    ctrl: false,
+    alt: false,
+    meta: false,
+};
+const SHIFT: KeyModState = KeyModState { shift: true, ..NONE };
+const CTRL: KeyModState = KeyModState { ctrl: true, ..NONE };
+const ALT_GR: KeyModState = KeyModState { alt: true, ..CTRL };
+
+// test metrics: 8px per glyph and 16px lines, plus 3px of padding
+fn new_driver(area: TextArea) -> EventDriver<Window<TextArea>> {
+    let id = area.get_id();
+    let mut window = Window::new(area);
+    window.set_size([200, 100]);
+    window.set_focus(Some(id));
+    EventDriver::new(window)
+}
+
+fn area(driver: &EventDriver<Window<TextArea>>) -> &TextArea {
+    &driver.window().child
+}
+
+#[test]
+fn gap_buffer_edit() {
+    let mut buf = GapBuffer::from("hello\nworld");
+    assert_eq!(buf.line_count(), 2);
+    assert_eq!(buf.line(1), "world");
+
+    buf.insert(5, ", dear");
+    buf.insert(0, "¡");
+    assert_eq!(buf.to_string(), "¡hello, dear\nworld");
+    assert_eq!(buf.slice(2..8), "hello,");
+    assert!(!buf.is_char_boundary(1));
+    assert_eq!(buf.next_char(0), 2);
+    assert_eq!(buf.prev_char(2), 0);
+
+    buf.replace(7..14, " big\nwide\n");
+    assert_eq!(buf.to_string(), "¡hello big\nwide\nworld");
+    assert_eq!(buf.line_count(), 3);
+    assert_eq!(buf.line_range(1), 12..16);
+    assert_eq!(buf.line_of(16), 1);
+    assert_eq!(buf.line_of(17), 2);
+
+    buf.remove(0..17);
+    assert_eq!(buf.to_string(), "world");
+    assert_eq!(buf.line_count(), 1);
+    assert_eq!(buf.len(), 5);
+
+    let long = "x".repeat(1000);
+    buf.insert(2, &long);
+    assert_eq!(buf.len(), 1005);
+    assert_eq!(buf.line(0), format!("wo{}rld", long));
+}
+
+#[test]
+fn text_area_editing() {
+    let mut driver = new_driver(TextArea::new([100, 56]).with_text("one\ntwo\nthree"));
+    driver.press_key(Key::End, CTRL);
+    assert_eq!(area(&driver).caret(), 13);
+
+    // vertical moves keep the horizontal offset
+    driver.press_key(Key::Up, NONE);
+    assert_eq!(area(&driver).caret_line_col(), (1, 3));
+    driver.press_key(Key::Up, NONE);
+    assert_eq!(area(&driver).caret_line_col(), (0, 3));
+    driver.press_key(Key::Down, NONE);
+    driver.press_key(Key::Down, NONE);
+    assert_eq!(area(&driver).caret_line_col(), (2, 5));
+
+    driver.press_key(Key::Home, NONE);
+    driver.press_key(Key::BackSpace, NONE);
+    assert_eq!(area(&driver).get_text(), "one\ntwothree");
+    driver.press_key(Key::Enter(false), NONE);
+    driver.type_text("2");
+    assert_eq!(area(&driver).get_text(), "one\ntwo\n2three");
+    assert_eq!(area(&driver).caret_line_col(), (2, 1));
+
+    driver.press_key(Key::Up, SHIFT);
+    assert_eq!(area(&driver).selected_text(), "wo\n2");
+    driver.press_key(Key::Delete, NONE);
+    assert_eq!(area(&driver).get_text(), "one\ntthree");
+}
+
+#[test]
+fn text_area_wrap() {
+    // 94px of content fit 11 glyphs per row
+    let mut driver = new_driver(TextArea::new([100, 56]).with_text("aaaa bbbb cccc dddd"));
+    driver.press_key(Key::Down, NONE);
+    assert_eq!(area(&driver).caret(), 10);
+    driver.press_key(Key::End, NONE);
+    assert_eq!(area(&driver).caret(), 19);
+
+    driver.click_at([20, 27], MouseButton::Left);
+    assert_eq!(area(&driver).caret(), 12);
+    driver.drag([4, 10], [28, 27]);
+    assert_eq!(area(&driver).selected_text(), "aaaa bbbb ccc");
+
+    let mut driver = new_driver(TextArea::new([100, 56]).with_wrap(false).with_text("aaaa bbbb cccc dddd"));
+    driver.press_key(Key::Down, NONE);
+    assert_eq!(area(&driver).caret(), 19);
+    // caret at x = 152, with 94 visible pixels
+    assert_eq!(area(&driver).viewport_origin(), Position::new(59, 0));
+}
+
+fn drawn_lines(driver: &EventDriver<Window<TextArea>>) -> Vec<String> {
+    let mut backend = TestBackend::default();
+    driver.window().draw(&mut backend);
+    backend
+        .draw_cmd
+        .iter()
+        .filter_map(|cmd| match cmd {
+            TestDrawCmd::Text { text, .. } => Some(text.text[0].text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn text_area_scroll() {
+    let text: Vec<_> = (0..10).map(|i| format!("line{}", i)).collect();
+    let mut driver = new_driver(TextArea::new([100, 56]).with_text(text.join("\n")));
+    assert_eq!(drawn_lines(&driver), ["line0", "line1", "line2", "line3"]);
+
+    driver.press_key(Key::End, CTRL);
+    assert_eq!(area(&driver).viewport_origin(), Position::new(0, 110));
+    assert_eq!(drawn_lines(&driver), ["line6", "line7", "line8", "line9"]);
+
+    // scrolls up a page, minus one line
+    driver.press_key(Key::PageUp, NONE);
+    assert_eq!(area(&driver).caret_line_col(), (7, 5));
+    assert_eq!(area(&driver).viewport_origin(), Position::new(0, 78));
+    driver.press_key(Key::PageDown, NONE);
+    assert_eq!(area(&driver).caret_line_col(), (9, 5));
+
+    driver.press_key(Key::Home, CTRL);
+    assert_eq!(area(&driver).viewport_origin(), Position::new(0, 0));
+    driver.move_to([50, 30]);
+    driver.scroll(0.0, -1.0);
+    assert_eq!(area(&driver).viewport_origin(), Position::new(0, 48));
+    assert_eq!(drawn_lines(&driver), ["line3", "line4", "line5", "line6"]);
+    // touchpads scroll by exact amounts
+    driver.scroll_pixels(0.0, 20.0);
+    assert_eq!(area(&driver).viewport_origin(), Position::new(0, 28));
+    driver.scroll_pixels(0.0, 100.0);
+    assert_eq!(area(&driver).viewport_origin(), Position::new(0, 0));
+    // the caret stays where it was
+    assert_eq!(area(&driver).caret(), 0);
+}
+
+#[test]
+fn text_area_row_offsets() {
+    let mut driver = new_driver(TextArea::new([100, 96]).with_text("aaaa bbbb cccc dddd\nxy\nzz"));
+    // the first line takes two rows
+    driver.click_at([5, 43], MouseButton::Left);
+    assert_eq!(area(&driver).caret_line_col(), (1, 0));
+
+    // now it takes four rows, the lines below move down
+    driver.press_key(Key::Home, CTRL);
+    driver.type_text("eeee ffff gggg ");
+    driver.click_at([5, 75], MouseButton::Left);
+    assert_eq!(area(&driver).caret_line_col(), (1, 0));
+    driver.click_at([5, 91], MouseButton::Left);
+    assert_eq!(area(&driver).caret_line_col(), (2, 0));
+    assert_eq!(drawn_lines(&driver), ["eeee ffff gggg aaaa bbbb cccc dddd", "xy", "zz"]);
+
+    // and back to a single row
+    driver.press_key(Key::Home, CTRL);
+    driver.press_key(Key::End, SHIFT);
+    driver.type_text("a");
+    driver.click_at([5, 27], MouseButton::Left);
+    assert_eq!(area(&driver).caret_line_col(), (1, 0));
+}
+
+#[test]
+fn text_area_altgr() {
+    let mut driver = new_driver(TextArea::new([100, 56]));
+    driver.set_modifiers(ALT_GR);
+    assert!(driver.push_event(Event::Character('{')));
+    driver.set_modifiers(CTRL);
+    assert!(!driver.push_event(Event::Character('x')));
+    assert_eq!(area(&driver).get_text(), "{");
+}
+
+#[test]
+fn text_area_read_only() {
+    let mut driver = new_driver(TextArea::new([100, 56]).with_read_only(true).with_text("log line"));
+    assert!(!driver.type_text("x"));
+    assert!(!driver.press_key(Key::BackSpace, NONE));
+    assert_eq!(area(&driver).get_text(), "log line");
+    driver.press_key(Key::Right, CTRL);
+    driver.press_key(Key::End, SHIFT);
+    assert_eq!(area(&driver).selected_text(), "line");
+}
+
+#[test]
+fn text_area_clipboard() {
+    let mut driver = new_driver(TextArea::new([100, 56]).with_text("first\nsecond"));
+    driver.press_key(Key::Down, SHIFT);
+    driver.press_key(Key::Letter('c'), CTRL);
+    assert_eq!(driver.resources().clipboard.get_text().as_deref(), Some("first\n"));
+
+    driver.press_key(Key::End, CTRL);
+    driver.resources().clipboard.set_text("\r\nthird").unwrap();
+    driver.press_key(Key::Letter('v'), CTRL);
+    assert_eq!(area(&driver).get_text(), "first\nsecond\nthird");
+    assert_eq!(area(&driver).caret_line_col(), (2, 5));
+
+    driver.press_key(Key::Up, SHIFT);
+    driver.press_key(Key::Letter('x'), CTRL);
+    assert_eq!(driver.resources().clipboard.get_text().as_deref(), Some("d\nthird"));
+    assert_eq!(area(&driver).get_text(), "first\nsecon");
+
+    // read only text can be copied but not cut
+    let mut driver = new_driver(TextArea::new([100, 56]).with_read_only(true).with_text("log"));
+    driver.press_key(Key::Letter('a'), CTRL);
+    assert!(!driver.press_key(Key::Letter('x'), CTRL));
+    driver.press_key(Key::Letter('c'), CTRL);
+    assert_eq!(driver.resources().clipboard.get_text().as_deref(), Some("log"));
+    assert_eq!(area(&driver).get_text(), "log");
+}
+
+#[test]
+fn text_area_ime() {
+    let mut driver = new_driver(TextArea::new([100, 56]).with_text("one\ntwo"));
+    driver.press_key(Key::End, CTRL);
+    driver.push_event(Event::ImePreedit {
+        text: "かな".into(),
+        cursor: Some((3, 3)),
+    });
+    assert_eq!(area(&driver).get_text(), "one\ntwo");
+    assert_eq!(area(&driver).ime_cursor_area(), Some(Rect::new([35, 19], [1, 16])));
+
+    driver.push_event(Event::ImeCommit("仮名".into()));
+    assert_eq!(area(&driver).get_text(), "one\ntwo仮名");
+    assert_eq!(area(&driver).ime_cursor_area(), Some(Rect::new([43, 19], [1, 16])));
+
+    // read only text areas ignore the input method
+    let mut driver = new_driver(TextArea::new([100, 56]).with_read_only(true));
+    assert!(!driver.push_event(Event::ImeCommit("x".into())));
+}
+
+#[test]
+fn text_area_unfocused() {
+    let mut window = Window::new(TextArea::new([100, 56]).with_text("one"));
+    window.set_size([200, 100]);
+    let mut driver = EventDriver::new(window);
+    // without focus the input is broadcast, the text must not change
+    assert!(!driver.type_text("two"));
+    assert!(!driver.press_key(Key::Enter(false), NONE));
+    assert!(!driver.push_event(Event::ImeCommit("仮名".into())));
+    assert_eq!(area(&driver).get_text(), "one");
+}
@@ -0,0 +1,217 @@
+use rtk::backend::Clipboard;
+use rtk::event::{Key, KeyModState, MouseButton};
+use rtk::prelude::*;
+use rtk::testing::{EventDriver, TestBackend, TestDrawCmd};
+use rtk::toplevel::{TopLevel, Window};
+use rtk_widgets::TextInput;
+
+const NONE: KeyModState = KeyModState {
+    shift: false,
+    ctrl: false,
+    alt: false,
+    meta: false,
+};
+const SHIFT: KeyModState = KeyModState { shift: true, ..NONE };
+const CTRL: KeyModState = KeyModState { ctrl: true, ..NONE };
+const CTRL_SHIFT: KeyModState = KeyModState { ctrl: true, ..SHIFT };
+const CTRL_ALT: KeyModState = KeyModState { alt: true, ..CTRL };
+
+fn driver(input: TextInput) -> EventDriver<Window<TextInput>> {
+    let id = input.get_id();
+    let mut window = Window::new(input);
+    window.set_focus(Some(id));
+    EventDriver::new(window)
+}
+
+fn input(driver: &EventDriver<Window<TextInput>>) -> &TextInput {
+    &driver.window().child
+}
+
+#[test]
+fn text_input_editing() {
+    let mut driver = driver(TextInput::new(100));
+    assert!(input(&driver).is_focused());
+    assert!(driver.type_text("hello"));
+    assert_eq!(input(&driver).get_text(), "hello");
+    assert_eq!(input(&driver).caret(), 5);
+
+    driver.press_key(Key::Left, NONE);
+    driver.press_key(Key::Left, NONE);
+    driver.press_key(Key::BackSpace, NONE);
+    assert_eq!(input(&driver).get_text(), "helo");
+    driver.press_key(Key::Delete, NONE);
+    assert_eq!(input(&driver).get_text(), "heo");
+    assert_eq!(input(&driver).caret(), 2);
+
+    driver.press_key(Key::Home, NONE);
+    driver.press_key(Key::End, SHIFT);
+    assert_eq!(input(&driver).selected_text(), "heo");
+    driver.type_text("Xy");
+    assert_eq!(input(&driver).get_text(), "Xy");
+
+    driver.press_key(Key::Letter('a'), CTRL);
+    assert_eq!(input(&driver).selection(), 0..2);
+    driver.press_key(Key::BackSpace, NONE);
+    assert_eq!(input(&driver).get_text(), "");
+}
+
+#[test]
+fn text_input_word_jumps() {
+    let mut driver = driver(TextInput::new(200).with_text("foo bar baz"));
+    driver.press_key(Key::Left, CTRL);
+    assert_eq!(input(&driver).caret(), 8);
+    driver.press_key(Key::Left, CTRL);
+    assert_eq!(input(&driver).caret(), 4);
+    driver.press_key(Key::Right, CTRL_SHIFT);
+    assert_eq!(input(&driver).selected_text(), "bar ");
+
+    // collapses the selection to it's end
+    driver.press_key(Key::Right, NONE);
+    assert_eq!(input(&driver).caret(), 8);
+    driver.press_key(Key::BackSpace, CTRL);
+    assert_eq!(input(&driver).get_text(), "foo baz");
+    driver.press_key(Key::Delete, CTRL);
+    assert_eq!(input(&driver).get_text(), "foo ");
+}
+
+#[test]
+fn text_input_mouse() {
+    // test metrics: 8px per glyph, plus 3px of padding
+    let mut driver = driver(TextInput::new(200).with_text("hello world"));
+    driver.click_at([20, 10], MouseButton::Left);
+    assert_eq!(input(&driver).caret(), 2);
+    assert_eq!(input(&driver).selection(), 2..2);
+
+    driver.drag([4, 10], [36, 10]);
+    assert_eq!(input(&driver).selected_text(), "hell");
+
+    driver.click_at([60, 10], MouseButton::Left);
+    driver.click_at([60, 10], MouseButton::Left);
+    assert_eq!(input(&driver).selected_text(), "world");
+}
+
+#[test]
+fn text_input_scroll() {
+    let mut driver = driver(TextInput::new(60));
+    driver.type_text("hello world");
+    // caret at x = 88, with 54 visible pixels
+    assert_eq!(input(&driver).viewport_origin(), Position::new(35, 0));
+    driver.press_key(Key::Home, NONE);
+    assert_eq!(input(&driver).viewport_origin(), Position::new(0, 0));
+}
+
+#[test]
+fn text_input_password() {
+    let mut driver = driver(TextInput::new(200).with_password('*').with_text("secret"));
+    driver.press_key(Key::Left, CTRL);
+    assert_eq!(input(&driver).caret(), 0);
+
+    let mut backend = TestBackend::default();
+    driver.window().draw(&mut backend);
+    let texts: Vec<_> = backend
+        .draw_cmd
+        .iter()
+        .filter_map(|cmd| match cmd {
+            TestDrawCmd::Text { text, .. } => Some(text.text[0].text.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts, vec!["******"]);
+}
+
+#[test]
+fn text_input_clipboard() {
+    let mut driver = driver(TextInput::new(200).with_text("copy paste"));
+    driver.press_key(Key::Left, CTRL_SHIFT);
+    driver.press_key(Key::Letter('x'), CTRL);
+    assert_eq!(input(&driver).get_text(), "copy ");
+    assert_eq!(driver.resources().clipboard.get_text().as_deref(), Some("paste"));
+
+    driver.press_key(Key::Home, NONE);
+    driver.press_key(Key::Letter('v'), CTRL);
+    assert_eq!(input(&driver).get_text(), "pastecopy ");
+
+    // line breaks are replaced by spaces
+    driver.resources().clipboard.set_text("one\r\ntwo\n").unwrap();
+    driver.press_key(Key::Letter('v'), CTRL);
+    assert_eq!(input(&driver).get_text(), "pasteone twocopy ");
+
+    driver.press_key(Key::Letter('a'), CTRL);
+    driver.press_key(Key::Letter('c'), CTRL);
+    assert_eq!(driver.resources().clipboard.get_text().as_deref(), Some("pasteone twocopy "));
+
+    // password inputs don't copy
+    let mut driver = self::driver(TextInput::new(200).with_password('*').with_text("secret"));
+    driver.press_key(Key::Letter('a'), CTRL);
+    driver.press_key(Key::Letter('c'), CTRL);
+    assert_eq!(driver.resources().clipboard.get_text(), None);
+}
+
+#[test]
+fn text_input_altgr() {
+    let mut driver = driver(TextInput::new(100));
+    // AltGr is reported as ctrl+alt
+    driver.set_modifiers(CTRL_ALT);
+    assert!(driver.push_event(Event::Character('@')));
+    // but plain ctrl is a shortcut
+    driver.set_modifiers(CTRL);
+    assert!(!driver.push_event(Event::Character('a')));
+    assert_eq!(input(&driver).get_text(), "@");
+}
+
+#[test]
+fn text_input_ime() {
+    let mut driver = driver(TextInput::new(200).with_text("ab"));
+    driver.press_key(Key::Left, SHIFT);
+    driver.push_event(Event::ImePreedit {
+        text: "にほ".into(),
+        cursor: Some((6, 6)),
+    });
+    // the composition replaces the selection, but isn't part of the text until committed
+    assert_eq!(input(&driver).get_text(), "a");
+    // caret after the composition text: 3px padding + 3 glyphs
+    assert_eq!(input(&driver).ime_cursor_area(), Some(Rect::new([27, 3], [1, 16])));
+
+    driver.push_event(Event::ImeCommit("日本".into()));
+    assert_eq!(input(&driver).get_text(), "a日本");
+    assert_eq!(input(&driver).caret(), 7);
+    assert_eq!(input(&driver).ime_cursor_area(), Some(Rect::new([27, 3], [1, 16])));
+}
+
+#[test]
+fn text_input_unfocused() {
+    let mut window = Window::new(TextInput::new(100).with_text("ab"));
+    window.set_size([200, 50]);
+    let mut driver = EventDriver::new(window);
+    assert!(!input(&driver).is_focused());
+    // without focus the input is broadcast, the text must not change
+    assert!(!driver.type_text("hello"));
+    assert!(!driver.press_key(Key::BackSpace, NONE));
+    assert!(!driver.push_event(Event::ImePreedit {
+        text: "にほ".into(),
+        cursor: None,
+    }));
+    assert!(!driver.push_event(Event::ImeCommit("日本".into())));
+    assert_eq!(input(&driver).get_text(), "ab");
+}
+
+#[test]
+fn text_input_measure() {
+    let mut resources = TestBackend::default();
+    let mut text_input = TextInput::new(100);
+    // 16px line and 3px padding
+    let hint = text_input.measure(Size::new(300, 300), &mut resources);
+    assert_eq!(hint.preferred, Size::new(100, 22));
+    assert!(hint.min.w < 100 && hint.max.w > 300);
+    assert_eq!(hint.min.h, 22);
+    assert_eq!(hint.max.h, 22);
+
+    text_input.arrange(Rect::new([5, 5], [150, 40]), &mut resources);
+    assert_eq!(text_input.get_bounds(), Rect::new([5, 5], [150, 22]));
+
+    // windows arrange the input with all their width
+    let mut window = Window::new(TextInput::new(100));
+    window.set_size([200, 50]);
+    let driver = EventDriver::new(window);
+    assert_eq!(input(&driver).get_size(), Size::new(200, 22));
+}